@@ -1,25 +1,93 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use std::time::Duration;
+use utils::error_report::{self, ErrorFormat};
+use utils::file_handler::FileHandler;
+use utils::format;
+use utils::logger::{self, ProgressFormat};
+use utils::signal;
 
 mod commands {
     pub mod benchmark;
+    pub mod benchmark_compare;
+    pub mod convert;
+    pub mod diff;
+    pub mod env;
+    pub mod fix;
+    pub mod grep;
+    pub mod hash;
     pub mod hello;
+    pub mod hexdump;
+    pub mod integrity_watch;
+    pub mod ls;
+    pub mod manifest;
+    pub mod process;
+    pub mod render;
+    pub mod verify;
     pub mod version;
+    pub mod watch;
 }
 
 mod utils {
+    pub mod assertion;
+    pub mod cache;
+    pub mod deprecation;
+    pub mod error_report;
     pub mod file_handler;
+    pub mod format;
+    pub mod json;
     pub mod logger;
+    pub mod parallel;
+    pub mod prompt;
+    pub mod signal;
+    pub mod table;
+    pub mod template;
+    pub mod watcher;
 }
 
-use commands::{benchmark::BenchmarkCommand, hello::HelloCommand, version::VersionCommand};
-use utils::logger::Logger;
+use commands::{
+    benchmark::BenchmarkCommand,
+    benchmark_compare::BenchmarkCompareCommand,
+    convert::{ConvertCommand, DataFormat},
+    diff::DiffCommand,
+    env::EnvCommand,
+    fix::FixCommand,
+    grep::GrepCommand,
+    hash::HashCommand,
+    hello::HelloCommand,
+    hexdump::HexdumpCommand,
+    integrity_watch::IntegrityWatchCommand,
+    ls::{ListFormat, LsCommand},
+    manifest::ManifestCommand,
+    process::{ProcessCommand, ProcessOptions},
+    render::RenderCommand,
+    verify::VerifyCommand,
+    version::VersionCommand,
+    watch::WatchCommand,
+};
 
 #[derive(Parser)]
 #[command(name = "basiccli-rust")]
 #[command(author, version, about = "BasicCli - High Performance CLI (Rust Version)", long_about = None)]
 struct Cli {
+    /// How to report errors on stderr: human (always exit code 1) or json
+    /// (sysexits-style exit codes: 65 bad data, 66 not found, 74 I/O, 77 permission denied)
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+
+    /// Exit non-zero if any warning or error was logged, even when the command succeeds
+    #[arg(long, global = true)]
+    fail_on_warn: bool,
+
+    /// How long operations report progress: bar, or json lines on stderr
+    #[arg(long, global = true, value_enum, default_value_t = ProgressFormat::Bar)]
+    progress_format: ProgressFormat,
+
+    /// Chunk size in bytes for streaming reads, copies and checksums (default 65536)
+    #[arg(long, global = true, value_name = "BYTES")]
+    buffer_size: Option<usize>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -38,6 +106,10 @@ enum Commands {
         /// Repeat the greeting N times
         #[arg(short, long, default_value_t = 1)]
         repeat: usize,
+
+        /// Greet in a loop for this many seconds and report the iteration count (soak testing)
+        #[arg(long, hide = true, value_name = "SECS")]
+        soak: Option<u64>,
     },
 
     /// Display version information
@@ -45,6 +117,21 @@ enum Commands {
         /// Output version info as JSON
         #[arg(long)]
         json: bool,
+
+        /// Print simple key: value lines instead of the boxed layout
+        #[arg(long)]
+        plain: bool,
+
+        /// Print the JSON on a single line
+        #[arg(long, requires = "json")]
+        compact: bool,
+    },
+
+    /// Display runtime environment details for bug reports
+    Env {
+        /// Output environment info as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Run performance benchmarks
@@ -53,19 +140,57 @@ enum Commands {
         #[arg(default_value_t = 1000)]
         iterations: usize,
 
-        /// Output format: console, json, or csv
+        /// Output format: console, json, yaml, csv, or none to print nothing (quiet is a deprecated alias)
         #[arg(short, long, default_value = "console")]
         output: String,
 
+        /// Also write the results as JSON to this file
+        #[arg(long, value_parser = expand_path)]
+        output_file: Option<PathBuf>,
+
+        /// Exit non-zero if any benchmark is slower than in this JSON results file
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
         /// Show detailed benchmark information
         #[arg(short, long)]
         verbose: bool,
+
+        /// Run each benchmark once and report PASS/FAIL instead of timings
+        #[arg(long)]
+        smoke: bool,
+
+        /// Stop after this many seconds and report partial results (0 = no limit)
+        #[arg(long, default_value_t = 0)]
+        timeout: u64,
+
+        /// Decimal places for numeric CSV and JSON fields
+        #[arg(long)]
+        precision: Option<usize>,
+
+        /// Group digits in console numbers using this locale, e.g. en or de
+        #[arg(long)]
+        locale: Option<String>,
+    },
+
+    /// Compare ops/sec between two `benchmark --output json` result files
+    BenchmarkCompare {
+        /// Baseline results
+        baseline: PathBuf,
+
+        /// Results to compare against the baseline
+        candidate: PathBuf,
     },
 
-    /// Process a JSON file and demonstrate file I/O
+    /// Process JSON files and demonstrate file I/O
     Process {
-        /// File to process
-        file: PathBuf,
+        /// Files to process
+        #[arg(required_unless_present = "glob")]
+        files: Vec<PathBuf>,
+
+        /// Also process files matching this glob pattern
+        #[arg(short, long)]
+        glob: Option<String>,
 
         /// Pretty print JSON output
         #[arg(short, long)]
@@ -74,80 +199,568 @@ enum Commands {
         /// Show processing statistics
         #[arg(short, long)]
         stats: bool,
+
+        /// Emit document statistics as a JSON object on stderr
+        #[arg(long)]
+        stats_json: bool,
+
+        /// Stop at the first file that fails to process
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Set a value by JSON pointer before printing, e.g. /a/b=1 (repeatable)
+        #[arg(long = "set", value_name = "POINTER=VALUE")]
+        set: Vec<String>,
+
+        /// Remove the value at a JSON pointer before printing (repeatable)
+        #[arg(long = "unset", value_name = "POINTER")]
+        unset: Vec<String>,
+
+        /// Keep only these comma-separated top-level keys
+        #[arg(long, value_delimiter = ',', conflicts_with = "except")]
+        only: Vec<String>,
+
+        /// Drop these comma-separated top-level keys
+        #[arg(long, value_delimiter = ',')]
+        except: Vec<String>,
+
+        /// Print only the node at this JSON pointer, e.g. /users/0/name
+        #[arg(short, long)]
+        query: Option<String>,
+
+        /// Stream a top-level array element by element instead of loading it whole
+        #[arg(long)]
+        stream_array: bool,
+
+        /// Pretty print with this many spaces of indentation
+        #[arg(long, conflicts_with = "tabs")]
+        indent: Option<usize>,
+
+        /// Pretty print with tab indentation
+        #[arg(long)]
+        tabs: bool,
+
+        /// Fail unless the document satisfies this check, e.g. '$.items length > 0' (repeatable)
+        #[arg(long = "assert", value_name = "EXPRESSION")]
+        asserts: Vec<String>,
+
+        /// Reject documents that repeat a key within an object
+        #[arg(long, conflicts_with = "stream_array")]
+        strict: bool,
+
+        /// Print RFC 8785 canonical JSON (sorted keys, no whitespace, canonical numbers)
+        #[arg(long, conflicts_with_all = ["pretty", "indent", "tabs"])]
+        canonical: bool,
+
+        /// Print only the first N elements of a top-level array
+        #[arg(long, value_name = "N", conflicts_with_all = ["tail", "stream_array"])]
+        head: Option<usize>,
+
+        /// Print only the last N elements of a top-level array
+        #[arg(long, value_name = "N", conflicts_with = "stream_array")]
+        tail: Option<usize>,
+    },
+
+    /// Render a template with values from a JSON file
+    Render {
+        /// Template file containing {{ path.to.value }} placeholders
+        #[arg(short, long)]
+        template: PathBuf,
+
+        /// JSON file providing the values
+        #[arg(short, long)]
+        data: PathBuf,
+
+        /// Render missing keys as empty strings instead of failing
+        #[arg(long)]
+        allow_missing: bool,
+    },
+
+    /// Show a hex dump of a binary file
+    Hexdump {
+        /// File to dump
+        file: PathBuf,
+
+        /// Byte offset to start dumping from
+        #[arg(short, long, default_value_t = 0)]
+        offset: u64,
+
+        /// Maximum number of bytes to dump
+        #[arg(short = 'n', long)]
+        length: Option<u64>,
+    },
+
+    /// Verify files against a checksum list such as SHA256SUMS
+    Verify {
+        /// Checksum file with `<hash>  <filename>` lines
+        checksum_file: PathBuf,
+
+        /// Hash algorithm (inferred from hash length when omitted)
+        #[arg(short, long)]
+        algorithm: Option<String>,
+    },
+
+    /// Print the checksums of files, or of stdin for `-`
+    Hash {
+        /// Files to hash; `-` reads standard input
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Hash algorithm: md5, sha1, sha256, or sha512
+        #[arg(short, long, default_value = "sha256")]
+        algorithm: String,
+
+        /// Hash this many files in parallel (0 = one per CPU)
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+    },
+
+    /// Print checksum lines for every file under a directory, e.g. to create SHA256SUMS
+    Manifest {
+        /// Directory to walk recursively
+        dir: PathBuf,
+
+        /// Hash algorithm: md5, sha1, sha256, or sha512
+        #[arg(short, long, default_value = "sha256")]
+        algorithm: String,
+    },
+
+    /// Trim trailing whitespace and add missing final newlines in place
+    Fix {
+        /// Files to fix
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Leave trailing whitespace alone
+        #[arg(long)]
+        no_trim: bool,
+
+        /// Do not add a newline at the end of files that lack one
+        #[arg(long)]
+        no_final_newline: bool,
+    },
+
+    /// Convert a data file between JSON, YAML and CSV
+    Convert {
+        /// Input file or quoted glob such as 'configs/*.yml'
+        input: PathBuf,
+
+        /// Source format (detected from each file's extension when omitted)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Target format: json, yaml, or csv (from a JSON array of objects)
+        #[arg(long)]
+        to: String,
+
+        /// Output file (defaults to the input path with the target extension)
+        #[arg(short, long, value_parser = expand_path)]
+        output: Option<PathBuf>,
+
+        /// Indent JSON output with this many spaces (default 2)
+        #[arg(long, conflicts_with = "tabs")]
+        indent: Option<usize>,
+
+        /// Indent JSON output with tabs
+        #[arg(long)]
+        tabs: bool,
+
+        /// Print the planned source -> target mapping without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Exit successfully even if some files in a batch fail to convert
+        #[arg(long)]
+        continue_on_error: bool,
+
+        /// Parse CSV cells that look like numbers or booleans into JSON values
+        #[arg(long)]
+        infer_types: bool,
+
+        /// Convert this many files of a batch in parallel (0 = one per CPU)
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+
+        /// Write RFC 8785 canonical JSON (requires --to json)
+        #[arg(long, conflicts_with_all = ["indent", "tabs"])]
+        canonical: bool,
+    },
+
+    /// Show a unified diff between two text files
+    Diff {
+        /// Original file
+        old: PathBuf,
+
+        /// Changed file
+        new: PathBuf,
+
+        /// Unchanged lines to show around each change
+        #[arg(short = 'U', long, default_value_t = 3)]
+        context: usize,
+    },
+
+    /// Print lines of text files matching a regular expression
+    Grep {
+        /// Regular expression to search for
+        pattern: String,
+
+        /// Files to search
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Print only the number of matching lines per file
+        #[arg(short, long)]
+        count: bool,
+
+        /// Select lines that do not match the pattern
+        #[arg(short = 'v', long)]
+        invert_match: bool,
+
+        /// Match regardless of letter case
+        #[arg(short, long)]
+        ignore_case: bool,
+
+        /// Print this many lines of context after each match
+        #[arg(short = 'A', long = "after-context", value_name = "N")]
+        after: Option<usize>,
+
+        /// Print this many lines of context before each match
+        #[arg(short = 'B', long = "before-context", value_name = "N")]
+        before: Option<usize>,
+
+        /// Print this many lines of context around each match (-A/-B take precedence)
+        #[arg(short = 'C', long = "context", value_name = "N")]
+        context: Option<usize>,
+    },
+
+    /// List a directory's entries with size, modification time and type
+    Ls {
+        /// Directory to list
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+
+        /// Output the listing as JSON
+        #[arg(long, conflicts_with = "csv")]
+        json: bool,
+
+        /// Output the listing as CSV
+        #[arg(long)]
+        csv: bool,
+
+        /// Write the JSON or CSV listing to this file instead of stdout
+        #[arg(short, long, value_parser = expand_path)]
+        output: Option<PathBuf>,
+
+        /// Report directory sizes as the total size of the files they contain
+        #[arg(long)]
+        recursive_size: bool,
+    },
+
+    /// Watch a file and report content changes
+    Watch {
+        /// File to watch
+        file: PathBuf,
+
+        /// Re-emit the file in this format (json or yaml) beside it on every change
+        #[arg(long)]
+        convert_to: Option<String>,
+
+        /// Stop watching after this many seconds (0 = until interrupted)
+        #[arg(long, default_value_t = 0)]
+        timeout: u64,
+    },
+
+    /// Re-hash files periodically and alert when they change, appear or disappear
+    IntegrityWatch {
+        /// Files or directories to monitor
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Checksum baseline to compare against; written on first run if absent
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Hash algorithm for a new baseline: md5, sha1, sha256, or sha512
+        #[arg(short, long, default_value = "sha256")]
+        algorithm: String,
+
+        /// Re-hash at least this often (seconds), even without filesystem notifications
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+
+        /// Stop watching after this many seconds (0 = until interrupted)
+        #[arg(long, default_value_t = 0)]
+        timeout: u64,
     },
 }
 
-fn main() -> Result<()> {
+fn main() {
     let cli = Cli::parse();
+    if let Some(bytes) = cli.buffer_size {
+        FileHandler::set_buffer_size(bytes);
+    }
 
-    match cli.command {
+    let result = run(cli.command, cli.progress_format).and_then(|()| {
+        if cli.fail_on_warn && logger::warnings_emitted() {
+            anyhow::bail!("Warnings were logged and --fail-on-warn is set");
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        eprintln!("{}", error_report::render(&err, cli.error_format));
+        std::process::exit(error_report::exit_code(&err, cli.error_format));
+    }
+}
+
+fn run(command: Commands, progress_format: ProgressFormat) -> Result<()> {
+    match command {
         Commands::Hello {
             name,
             uppercase,
             repeat,
+            soak,
+        } => {
+            let mut command =
+                HelloCommand::new(name, uppercase, repeat).with_soak(soak.map(Duration::from_secs));
+            if soak.is_some() {
+                command = command.with_interrupt_flag(signal::install_interrupt_handler()?);
+            }
+            command.execute()?;
+        }
+        Commands::Version {
+            json,
+            plain,
+            compact,
         } => {
-            let command = HelloCommand::new(name, uppercase, repeat);
+            let command = VersionCommand::new(json, plain).with_compact(compact);
             command.execute()?;
         }
-        Commands::Version { json } => {
-            let command = VersionCommand::new(json);
+        Commands::Env { json } => {
+            let command = EnvCommand::new(json);
             command.execute()?;
         }
         Commands::Benchmark {
             iterations,
             output,
+            output_file,
+            baseline,
             verbose,
+            smoke,
+            timeout,
+            precision,
+            locale,
+        } => {
+            let locale = locale.as_deref().map(format::locale).transpose()?;
+            let command = BenchmarkCommand::new(iterations, output, verbose)
+                .with_smoke(smoke)
+                .with_timeout(Duration::from_secs(timeout))
+                .with_precision(precision)
+                .with_output_file(output_file)
+                .with_baseline(baseline)
+                .with_locale(locale)
+                .with_progress_format(progress_format);
+            command.execute()?;
+        }
+        Commands::BenchmarkCompare {
+            baseline,
+            candidate,
         } => {
-            let command = BenchmarkCommand::new(iterations, output, verbose);
+            let command = BenchmarkCompareCommand::new(baseline, candidate);
             command.execute()?;
         }
         Commands::Process {
-            file,
+            files,
+            glob,
             pretty,
             stats,
+            stats_json,
+            fail_fast,
+            set,
+            unset,
+            only,
+            except,
+            query,
+            stream_array,
+            indent,
+            tabs,
+            asserts,
+            strict,
+            canonical,
+            head,
+            tail,
         } => {
-            process_file(file, pretty, stats)?;
+            let options = ProcessOptions {
+                glob,
+                pretty,
+                stats,
+                stats_json,
+                fail_fast,
+                set,
+                unset,
+                only,
+                except,
+                query,
+                stream_array,
+                indent: indent_unit(indent, tabs),
+                asserts,
+                strict,
+                canonical,
+                head,
+                tail,
+            };
+            let command = ProcessCommand::new(files, options);
+            command.execute()?;
+        }
+        Commands::Render {
+            template,
+            data,
+            allow_missing,
+        } => {
+            let command = RenderCommand::new(template, data, allow_missing);
+            command.execute()?;
+        }
+        Commands::Hexdump {
+            file,
+            offset,
+            length,
+        } => {
+            let command = HexdumpCommand::new(file, offset, length);
+            command.execute()?;
+        }
+        Commands::Verify {
+            checksum_file,
+            algorithm,
+        } => {
+            let command = VerifyCommand::new(checksum_file, algorithm);
+            command.execute()?;
+        }
+        Commands::Hash {
+            files,
+            algorithm,
+            jobs,
+        } => {
+            let command = HashCommand::new(files, algorithm).with_jobs(jobs);
+            command.execute()?;
+        }
+        Commands::Manifest { dir, algorithm } => {
+            let command = ManifestCommand::new(dir, algorithm);
+            command.execute()?;
+        }
+        Commands::Fix {
+            files,
+            no_trim,
+            no_final_newline,
+        } => {
+            let command = FixCommand::new(files, !no_trim, !no_final_newline);
+            command.execute()?;
+        }
+        Commands::Convert {
+            input,
+            from,
+            to,
+            output,
+            indent,
+            tabs,
+            dry_run,
+            continue_on_error,
+            infer_types,
+            jobs,
+            canonical,
+        } => {
+            let from = from.as_deref().map(DataFormat::from_name).transpose()?;
+            let command = ConvertCommand::new(input, DataFormat::from_name(&to)?, output)
+                .with_from(from)
+                .with_indent(indent_unit(indent, tabs))
+                .with_dry_run(dry_run)
+                .with_continue_on_error(continue_on_error)
+                .with_infer_types(infer_types)
+                .with_canonical(canonical)
+                .with_jobs(jobs);
+            command.execute()?;
+        }
+        Commands::Diff { old, new, context } => {
+            let command = DiffCommand::new(old, new, context);
+            command.execute()?;
+        }
+        Commands::Grep {
+            pattern,
+            files,
+            count,
+            invert_match,
+            ignore_case,
+            after,
+            before,
+            context,
+        } => {
+            let before = before.or(context).unwrap_or(0);
+            let after = after.or(context).unwrap_or(0);
+            let command = GrepCommand::new(pattern, files)
+                .with_count(count)
+                .with_invert_match(invert_match)
+                .with_ignore_case(ignore_case)
+                .with_context(before, after);
+            command.execute()?;
+        }
+        Commands::Ls {
+            dir,
+            json,
+            csv,
+            output,
+            recursive_size,
+        } => {
+            let format = match (json, csv) {
+                (true, _) => ListFormat::Json,
+                (_, true) => ListFormat::Csv,
+                _ => ListFormat::Plain,
+            };
+            let command = LsCommand::new(dir, format)
+                .with_output(output)
+                .with_recursive_size(recursive_size);
+            command.execute()?;
+        }
+        Commands::Watch {
+            file,
+            convert_to,
+            timeout,
+        } => {
+            let convert_to = convert_to
+                .as_deref()
+                .map(DataFormat::from_name)
+                .transpose()?;
+            let command =
+                WatchCommand::new(file, convert_to).with_timeout(Duration::from_secs(timeout));
+            command.execute()?;
+        }
+        Commands::IntegrityWatch {
+            paths,
+            baseline,
+            algorithm,
+            interval,
+            timeout,
+        } => {
+            let command = IntegrityWatchCommand::new(paths, baseline)
+                .with_algorithm(algorithm)
+                .with_interval(Duration::from_secs(interval))
+                .with_timeout(Duration::from_secs(timeout))
+                .with_interrupt_flag(signal::install_interrupt_handler()?);
+            command.execute()?;
         }
     }
 
     Ok(())
 }
 
-fn process_file(file: PathBuf, pretty: bool, stats: bool) -> Result<()> {
-    let logger = Logger::new(if stats {
-        utils::logger::LogLevel::Debug
-    } else {
-        utils::logger::LogLevel::Info
-    });
-
-    logger.info(&format!("Processing file: {}", file.display()));
-
-    if !file.exists() {
-        logger.error(&format!("File not found: {}", file.display()));
-        std::process::exit(1);
-    }
-
-    let content = std::fs::read_to_string(&file)?;
-    let data: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
-        logger.error(&format!("Invalid JSON: {}", e));
-        e
-    })?;
-
-    if let Some(obj) = data.as_object() {
-        logger.info(&format!("Successfully parsed JSON with {} keys", obj.len()));
-    }
+/// Expands `~` and `$VAR` in output paths, so quoted or config-supplied
+/// paths like `$HOME/reports/out.json` work without a shell.
+fn expand_path(path: &str) -> std::result::Result<PathBuf, String> {
+    FileHandler::expand_path(path).map_err(|e| e.to_string())
+}
 
-    if pretty {
-        println!("{}", serde_json::to_string_pretty(&data)?);
+/// Turns `--indent n` / `--tabs` into the indent unit used for pretty JSON.
+fn indent_unit(indent: Option<usize>, tabs: bool) -> Option<String> {
+    if tabs {
+        Some("\t".to_string())
     } else {
-        println!("{}", serde_json::to_string(&data)?);
-    }
-
-    if stats {
-        let metadata = std::fs::metadata(&file)?;
-        logger.info(&format!("File size: {} bytes", metadata.len()));
-        logger.info("Processing complete");
+        indent.map(|n| " ".repeat(n))
     }
-
-    Ok(())
 }
@@ -1,14 +1,39 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use num_format::Locale;
+use regex::Regex;
 use serde_json::json;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
 
+use crate::commands::benchmark_compare;
+use crate::utils::deprecation;
+use crate::utils::file_handler::{FileHandler, GrepOptions};
+use crate::utils::format;
+use crate::utils::logger::{LogLevel, Logger, ProgressFormat};
+
+const CHECKSUM_BUFFER_SIZE: usize = 64 * 1024;
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+const GREP_BUFFER_LINES: usize = 5000;
+const BUFFERED_FILE_SIZE: usize = 256 * 1024;
+const LOGGER_LINES: usize = 1000;
+
 pub struct BenchmarkCommand {
     iterations: usize,
     output_format: String,
     verbose: bool,
+    smoke: bool,
+    timeout: Option<Duration>,
+    precision: Option<usize>,
+    output_file: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    locale: Option<Locale>,
+    progress_format: ProgressFormat,
+    deadline: Cell<Option<Instant>>,
 }
 
 #[derive(Debug, Clone)]
@@ -18,18 +43,140 @@ struct BenchmarkResult {
     total_time: Duration,
     avg_time: Duration,
     ops_per_sec: f64,
+    throughput_mb_s: Option<f64>,
+    lines_per_sec: Option<f64>,
+    timed_out: bool,
 }
 
+impl BenchmarkResult {
+    fn new(name: &str, iterations: usize, total_time: Duration, timed_out: bool) -> Self {
+        let (avg_time, ops_per_sec) = if iterations == 0 {
+            (Duration::ZERO, 0.0)
+        } else {
+            (
+                total_time / iterations as u32,
+                iterations as f64 / total_time.as_secs_f64(),
+            )
+        };
+
+        Self {
+            name: name.to_string(),
+            iterations,
+            total_time,
+            avg_time,
+            ops_per_sec,
+            throughput_mb_s: None,
+            lines_per_sec: None,
+            timed_out,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SmokeResult {
+    name: String,
+    passed: bool,
+}
+
+type BenchmarkFn = fn(&BenchmarkCommand) -> BenchmarkResult;
+
+const BENCHMARKS: &[(&str, BenchmarkFn)] = &[
+    (
+        "String Manipulation",
+        BenchmarkCommand::benchmark_string_manipulation,
+    ),
+    (
+        "Array Operations",
+        BenchmarkCommand::benchmark_array_operations,
+    ),
+    ("File I/O", BenchmarkCommand::benchmark_file_io),
+    ("JSON Parsing", BenchmarkCommand::benchmark_json_parsing),
+    (
+        "Hash Operations",
+        BenchmarkCommand::benchmark_hash_operations,
+    ),
+    ("Checksum (MD5)", |cmd| cmd.benchmark_checksum("md5")),
+    ("Checksum (SHA1)", |cmd| cmd.benchmark_checksum("sha1")),
+    ("Checksum (SHA256)", |cmd| cmd.benchmark_checksum("sha256")),
+    ("Checksum (SHA512)", |cmd| cmd.benchmark_checksum("sha512")),
+    ("Checksum (4 KiB buffer)", |cmd| {
+        cmd.benchmark_buffer_size("Checksum (4 KiB buffer)", 4 * 1024)
+    }),
+    ("Checksum (1 MiB buffer)", |cmd| {
+        cmd.benchmark_buffer_size("Checksum (1 MiB buffer)", 1024 * 1024)
+    }),
+    ("Grep", BenchmarkCommand::benchmark_grep),
+    ("Logger (plain)", |cmd| cmd.benchmark_logger(false)),
+    ("Logger (colored)", |cmd| cmd.benchmark_logger(true)),
+];
+
 impl BenchmarkCommand {
     pub fn new(iterations: usize, output_format: String, verbose: bool) -> Self {
         Self {
             iterations,
             output_format,
             verbose,
+            smoke: false,
+            timeout: None,
+            precision: None,
+            output_file: None,
+            baseline: None,
+            locale: None,
+            progress_format: ProgressFormat::Bar,
+            deadline: Cell::new(None),
         }
     }
 
+    /// Number of decimals for numeric CSV and JSON fields. Without it CSV keeps
+    /// 6/9/2 places for total/avg/ops and JSON keeps full precision.
+    pub fn with_precision(mut self, precision: Option<usize>) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Also writes the results as JSON to this file, whatever the console format.
+    pub fn with_output_file(mut self, output_file: Option<PathBuf>) -> Self {
+        self.output_file = output_file;
+        self
+    }
+
+    /// Fails the run if any benchmark is slower than in this `--output json` results file.
+    pub fn with_baseline(mut self, baseline: Option<PathBuf>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Groups digits in console numbers using this locale's separators.
+    /// JSON and CSV output are unaffected.
+    pub fn with_locale(mut self, locale: Option<Locale>) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// With `Json`, reports each finished benchmark as a progress event on stderr.
+    pub fn with_progress_format(mut self, progress_format: ProgressFormat) -> Self {
+        self.progress_format = progress_format;
+        self
+    }
+
+    /// Caps the total run time; benchmarks stop between iterations once it is spent.
+    /// A zero duration means no limit.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// Runs every benchmark once and reports PASS/FAIL instead of timings.
+    pub fn with_smoke(mut self, smoke: bool) -> Self {
+        self.smoke = smoke;
+        self
+    }
+
     pub fn execute(&self) -> Result<()> {
+        if self.smoke {
+            return self.execute_smoke();
+        }
+
         if self.verbose {
             println!("Running benchmarks with {} iterations...", self.iterations);
         }
@@ -38,27 +185,127 @@ impl BenchmarkCommand {
 
         match self.output_format.as_str() {
             "json" => self.output_json(&results),
+            "yaml" => self.output_yaml(&results),
             "csv" => self.output_csv(&results),
+            "none" => {}
+            "quiet" => {
+                deprecation::warn_once("--output quiet", "--output none");
+            }
             _ => self.output_console(&results),
         }
 
+        if let Some(path) = &self.output_file {
+            FileHandler::atomic_write(path, &self.format_json(&results))?;
+        }
+        self.check_baseline(&results)
+    }
+
+    fn check_baseline(&self, results: &[BenchmarkResult]) -> Result<()> {
+        let Some(baseline) = &self.baseline else {
+            return Ok(());
+        };
+
+        let current: Vec<(String, f64)> = results
+            .iter()
+            .map(|r| (r.name.clone(), r.ops_per_sec))
+            .collect();
+        let regressed = benchmark_compare::regressions(baseline, &current)?;
+        if !regressed.is_empty() {
+            let names: Vec<String> = regressed
+                .iter()
+                .map(|(name, delta)| format!("{} ({:+.1}%)", name, delta))
+                .collect();
+            bail!(
+                "{} benchmarks regressed against {}: {}",
+                regressed.len(),
+                baseline.display(),
+                names.join(", ")
+            );
+        }
         Ok(())
     }
 
     fn run_benchmarks(&self) -> Vec<BenchmarkResult> {
-        vec![
-            self.benchmark_string_manipulation(),
-            self.benchmark_array_operations(),
-            self.benchmark_file_io(),
-            self.benchmark_json_parsing(),
-            self.benchmark_hash_operations(),
-        ]
+        self.deadline
+            .set(self.timeout.map(|timeout| Instant::now() + timeout));
+
+        let progress = (self.progress_format == ProgressFormat::Json)
+            .then(|| Logger::new(LogLevel::Info).with_progress_format(ProgressFormat::Json));
+        BENCHMARKS
+            .iter()
+            .enumerate()
+            .map(|(index, (name, benchmark))| {
+                let result = benchmark(self);
+                if let Some(logger) = &progress {
+                    logger.progress(index + 1, BENCHMARKS.len(), name);
+                }
+                result
+            })
+            .collect()
+    }
+
+    fn execute_smoke(&self) -> Result<()> {
+        let results = self.run_smoke();
+
+        if self.output_format == "json" {
+            let output = json!({
+                "smoke": true,
+                "benchmarks": results.iter().map(|r| {
+                    json!({
+                        "name": r.name,
+                        "status": if r.passed { "PASS" } else { "FAIL" }
+                    })
+                }).collect::<Vec<_>>()
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            for result in &results {
+                let status = if result.passed { "PASS" } else { "FAIL" };
+                println!("{:5} {}", status, result.name);
+            }
+        }
+
+        let failed = results.iter().filter(|r| !r.passed).count();
+        if failed > 0 {
+            anyhow::bail!("{} of {} benchmarks failed", failed, results.len());
+        }
+
+        Ok(())
+    }
+
+    fn run_smoke(&self) -> Vec<SmokeResult> {
+        let runner = Self::new(1, self.output_format.clone(), false);
+
+        BENCHMARKS
+            .iter()
+            .map(|(name, benchmark)| {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| benchmark(&runner)));
+                SmokeResult {
+                    name: name.to_string(),
+                    passed: outcome.is_ok_and(|r| r.iterations == 1),
+                }
+            })
+            .collect()
+    }
+
+    /// Iteration indices that stop early once the run's deadline has passed.
+    fn budgeted_iterations(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.iterations).take_while(|_| {
+            self.deadline
+                .get()
+                .is_none_or(|deadline| Instant::now() < deadline)
+        })
+    }
+
+    fn finish(&self, name: &str, completed: usize, duration: Duration) -> BenchmarkResult {
+        BenchmarkResult::new(name, completed, duration, completed < self.iterations)
     }
 
     fn benchmark_string_manipulation(&self) -> BenchmarkResult {
         let start = Instant::now();
 
-        for i in 0..self.iterations {
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
             let mut s = format!("Hello World {}", i);
             s = s.to_uppercase();
             s = s.chars().rev().collect();
@@ -68,62 +315,49 @@ impl BenchmarkCommand {
                 .map(|c| c.to_string())
                 .collect::<Vec<_>>()
                 .join("-");
+            completed = i + 1;
         }
 
         let duration = start.elapsed();
 
-        BenchmarkResult {
-            name: "String Manipulation".to_string(),
-            iterations: self.iterations,
-            total_time: duration,
-            avg_time: duration / self.iterations as u32,
-            ops_per_sec: self.iterations as f64 / duration.as_secs_f64(),
-        }
+        self.finish("String Manipulation", completed, duration)
     }
 
     fn benchmark_array_operations(&self) -> BenchmarkResult {
         let start = Instant::now();
 
-        for _ in 0..self.iterations {
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
             let mut arr: Vec<i32> = (1..=100).collect();
             arr = arr.iter().map(|n| n * 2).collect();
             arr.retain(|n| n % 3 == 0);
             arr.sort_unstable();
             arr.reverse();
             let _: i32 = arr.iter().sum();
+            completed = i + 1;
         }
 
         let duration = start.elapsed();
 
-        BenchmarkResult {
-            name: "Array Operations".to_string(),
-            iterations: self.iterations,
-            total_time: duration,
-            avg_time: duration / self.iterations as u32,
-            ops_per_sec: self.iterations as f64 / duration.as_secs_f64(),
-        }
+        self.finish("Array Operations", completed, duration)
     }
 
     fn benchmark_file_io(&self) -> BenchmarkResult {
         let start = Instant::now();
 
+        let mut completed = 0;
         if let Ok(mut file) = NamedTempFile::new() {
-            for i in 0..self.iterations {
+            for i in self.budgeted_iterations() {
                 let content = format!("Line {}: {}\n", i, "x".repeat(100));
                 let _ = file.write_all(content.as_bytes());
                 let _ = file.flush();
+                completed = i + 1;
             }
         }
 
         let duration = start.elapsed();
 
-        BenchmarkResult {
-            name: "File I/O".to_string(),
-            iterations: self.iterations,
-            total_time: duration,
-            avg_time: duration / self.iterations as u32,
-            ops_per_sec: self.iterations as f64 / duration.as_secs_f64(),
-        }
+        self.finish("File I/O", completed, duration)
     }
 
     fn benchmark_json_parsing(&self) -> BenchmarkResult {
@@ -145,27 +379,24 @@ impl BenchmarkCommand {
 
         let start = Instant::now();
 
-        for _ in 0..self.iterations {
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
             if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&json_string) {
                 let _ = serde_json::to_string(&parsed);
             }
+            completed = i + 1;
         }
 
         let duration = start.elapsed();
 
-        BenchmarkResult {
-            name: "JSON Parsing".to_string(),
-            iterations: self.iterations,
-            total_time: duration,
-            avg_time: duration / self.iterations as u32,
-            ops_per_sec: self.iterations as f64 / duration.as_secs_f64(),
-        }
+        self.finish("JSON Parsing", completed, duration)
     }
 
     fn benchmark_hash_operations(&self) -> BenchmarkResult {
         let start = Instant::now();
 
-        for _ in 0..self.iterations {
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
             let mut map = HashMap::new();
             for i in 0..100 {
                 map.insert(format!("key_{}", i), i * 2);
@@ -175,17 +406,138 @@ impl BenchmarkCommand {
             let _: i32 = map.values().sum();
             map.insert("extra".to_string(), 999);
             let _: HashMap<_, _> = map.into_iter().filter(|(_, v)| *v > 50).collect();
+            completed = i + 1;
+        }
+
+        let duration = start.elapsed();
+
+        self.finish("Hash Operations", completed, duration)
+    }
+
+    fn benchmark_checksum(&self, algorithm: &str) -> BenchmarkResult {
+        let buffer: Vec<u8> = (0..CHECKSUM_BUFFER_SIZE).map(|i| (i % 251) as u8).collect();
+
+        let start = Instant::now();
+
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
+            let _ = FileHandler::checksum_bytes(&buffer, algorithm);
+            completed = i + 1;
+        }
+
+        let duration = start.elapsed();
+        let total_bytes = (CHECKSUM_BUFFER_SIZE * completed) as f64;
+
+        let name = format!("Checksum ({})", algorithm.to_uppercase());
+        let mut result = self.finish(&name, completed, duration);
+        result.throughput_mb_s = Some(total_bytes / BYTES_PER_MB / duration.as_secs_f64());
+        result
+    }
+
+    /// SHA-256 of a temp file read in `buffer_size` chunks, to compare chunk sizes.
+    fn benchmark_buffer_size(&self, name: &str, buffer_size: usize) -> BenchmarkResult {
+        let data: Vec<u8> = (0..BUFFERED_FILE_SIZE).map(|i| (i % 251) as u8).collect();
+        let file = NamedTempFile::new().and_then(|mut file| {
+            file.write_all(&data)?;
+            file.flush()?;
+            Ok(file)
+        });
+
+        let start = Instant::now();
+
+        let mut completed = 0;
+        if let Ok(file) = &file {
+            for i in self.budgeted_iterations() {
+                if let Ok(reader) = std::fs::File::open(file.path()) {
+                    let _ = FileHandler::checksum_reader_buffered(reader, "sha256", buffer_size);
+                }
+                completed = i + 1;
+            }
+        }
+
+        let duration = start.elapsed();
+        let total_bytes = (BUFFERED_FILE_SIZE * completed) as f64;
+
+        let mut result = self.finish(name, completed, duration);
+        result.throughput_mb_s = Some(total_bytes / BYTES_PER_MB / duration.as_secs_f64());
+        result
+    }
+
+    fn benchmark_grep(&self) -> BenchmarkResult {
+        let buffer: String = (0..GREP_BUFFER_LINES)
+            .map(|i| match i % 4 {
+                0 => format!(
+                    "2025-01-15T10:{:02}:00Z ERROR [db-{}] timeout after {}ms\n",
+                    i % 60,
+                    i % 7,
+                    i
+                ),
+                1 => format!(
+                    "2025-01-15T10:{:02}:01Z INFO request id={} path=/api/v1/users\n",
+                    i % 60,
+                    i
+                ),
+                2 => format!(
+                    "2025-01-15T10:{:02}:02Z WARN [cache-{}] miss ratio {}%\n",
+                    i % 60,
+                    i % 3,
+                    i % 100
+                ),
+                _ => format!("2025-01-15T10:{:02}:03Z DEBUG heartbeat {}\n", i % 60, i),
+            })
+            .collect();
+        let pattern = Regex::new(r"(?i)\b(error|warn)\b\s+\[(db|cache)-\d+\]\s+\w+.*\d+(ms|%)$")
+            .expect("valid benchmark regex");
+
+        let start = Instant::now();
+
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
+            let _ = FileHandler::grep_reader(buffer.as_bytes(), &pattern, &GrepOptions::default());
+            completed = i + 1;
         }
 
         let duration = start.elapsed();
+        let total_lines = (GREP_BUFFER_LINES * completed) as f64;
+
+        let mut result = self.finish("Grep", completed, duration);
+        result.lines_per_sec = Some(total_lines / duration.as_secs_f64());
+        result
+    }
 
-        BenchmarkResult {
-            name: "Hash Operations".to_string(),
-            iterations: self.iterations,
-            total_time: duration,
-            avg_time: duration / self.iterations as u32,
-            ops_per_sec: self.iterations as f64 / duration.as_secs_f64(),
+    fn benchmark_logger(&self, use_colors: bool) -> BenchmarkResult {
+        let logger =
+            Logger::new_with_options(LogLevel::Info, use_colors).with_writer(std::io::sink());
+        // `colored` drops escapes when stdout is not a terminal (CI, pipes), which
+        // would make the colored run measure the plain path.
+        if use_colors {
+            colored::control::set_override(true);
+        }
+
+        let start = Instant::now();
+
+        let mut completed = 0;
+        for i in self.budgeted_iterations() {
+            for line in 0..LOGGER_LINES {
+                logger.info(&format!("processed record {} of batch {}", line, i));
+            }
+            completed = i + 1;
+        }
+
+        let duration = start.elapsed();
+        if use_colors {
+            colored::control::unset_override();
         }
+        let total_lines = (LOGGER_LINES * completed) as f64;
+
+        let name = if use_colors {
+            "Logger (colored)"
+        } else {
+            "Logger (plain)"
+        };
+        let mut result = self.finish(name, completed, duration);
+        result.lines_per_sec = Some(total_lines / duration.as_secs_f64());
+        result
     }
 
     fn output_console(&self, results: &[BenchmarkResult]) {
@@ -194,11 +546,24 @@ impl BenchmarkCommand {
         println!("{}", "=".repeat(60));
 
         for result in results {
-            println!("\n{}:", result.name);
+            if result.timed_out {
+                println!("\n{}: (timed out)", result.name);
+            } else {
+                println!("\n{}:", result.name);
+            }
             println!("  Iterations:     {}", result.iterations);
             println!("  Total time:     {}", format_duration(result.total_time));
             println!("  Avg time/op:    {}", format_duration(result.avg_time));
-            println!("  Ops/second:     {:.2}", result.ops_per_sec);
+            println!(
+                "  Ops/second:     {}",
+                self.format_number(result.ops_per_sec)
+            );
+            if let Some(throughput) = result.throughput_mb_s {
+                println!("  Throughput:     {} MB/s", self.format_number(throughput));
+            }
+            if let Some(lines_per_sec) = result.lines_per_sec {
+                println!("  Lines/second:   {}", self.format_number(lines_per_sec));
+            }
         }
 
         let total_time: Duration = results.iter().map(|r| r.total_time).sum();
@@ -207,40 +572,101 @@ impl BenchmarkCommand {
         println!("{}", "=".repeat(60));
     }
 
+    /// Two-decimal console number, grouped when a locale is set.
+    fn format_number(&self, value: f64) -> String {
+        match &self.locale {
+            Some(locale) => format::grouped(value, 2, locale),
+            None => format!("{:.2}", value),
+        }
+    }
+
     fn output_json(&self, results: &[BenchmarkResult]) {
-        let output = json!({
+        println!("{}", self.format_json(results));
+    }
+
+    fn format_json(&self, results: &[BenchmarkResult]) -> String {
+        serde_json::to_string_pretty(&self.envelope(results)).unwrap()
+    }
+
+    fn output_yaml(&self, results: &[BenchmarkResult]) {
+        print!("{}", self.format_yaml(results));
+    }
+
+    /// The JSON envelope, serialized as YAML.
+    fn format_yaml(&self, results: &[BenchmarkResult]) -> String {
+        serde_yaml::to_string(&self.envelope(results)).unwrap()
+    }
+
+    fn envelope(&self, results: &[BenchmarkResult]) -> serde_json::Value {
+        let benchmarks = results.iter().map(|r| {
+            let (total_time_ms, avg_time_ms, ops_per_sec, throughput_mb_s, lines_per_sec) =
+                match self.precision {
+                    Some(precision) => (
+                        json!(round_to(r.total_time.as_secs_f64() * 1000.0, precision)),
+                        round_to(r.avg_time.as_secs_f64() * 1000.0, precision),
+                        round_to(r.ops_per_sec, precision),
+                        r.throughput_mb_s.map(|t| round_to(t, precision)),
+                        r.lines_per_sec.map(|l| round_to(l, precision)),
+                    ),
+                    None => (
+                        json!(r.total_time.as_millis()),
+                        r.avg_time.as_micros() as f64 / 1000.0,
+                        r.ops_per_sec,
+                        r.throughput_mb_s,
+                        r.lines_per_sec,
+                    ),
+                };
+            json!({
+                "name": r.name,
+                "iterations": r.iterations,
+                "total_time_ms": total_time_ms,
+                "avg_time_ms": avg_time_ms,
+                "ops_per_second": ops_per_sec,
+                "throughput_mb_s": throughput_mb_s,
+                "lines_per_second": lines_per_sec,
+                "timed_out": r.timed_out
+            })
+        });
+
+        json!({
             "timestamp": chrono::Utc::now().to_rfc3339(),
             "platform": std::env::consts::OS,
             "ruby_version": format!("Rust {}", env!("CARGO_PKG_RUST_VERSION")),
-            "benchmarks": results.iter().map(|r| {
-                json!({
-                    "name": r.name,
-                    "iterations": r.iterations,
-                    "total_time_ms": r.total_time.as_millis(),
-                    "avg_time_ms": r.avg_time.as_micros() as f64 / 1000.0,
-                    "ops_per_second": r.ops_per_sec
-                })
-            }).collect::<Vec<_>>()
-        });
-
-        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            "benchmarks": benchmarks.collect::<Vec<_>>()
+        })
     }
 
     fn output_csv(&self, results: &[BenchmarkResult]) {
-        println!("Benchmark,Iterations,Total Time (s),Avg Time (s),Ops/Second");
+        print!("{}", self.format_csv(results));
+    }
+
+    fn format_csv(&self, results: &[BenchmarkResult]) -> String {
+        let (total_places, avg_places, ops_places) =
+            self.precision.map_or((6, 9, 2), |p| (p, p, p));
+
+        let mut csv = String::from("Benchmark,Iterations,Total Time (s),Avg Time (s),Ops/Second\n");
         for r in results {
-            println!(
-                "{},{},{:.6},{:.9},{:.2}",
+            csv.push_str(&format!(
+                "{},{},{:.*},{:.*},{:.*}\n",
                 r.name,
                 r.iterations,
+                total_places,
                 r.total_time.as_secs_f64(),
+                avg_places,
                 r.avg_time.as_secs_f64(),
+                ops_places,
                 r.ops_per_sec
-            );
+            ));
         }
+        csv
     }
 }
 
+fn round_to(value: f64, places: usize) -> f64 {
+    let factor = 10f64.powi(places.min(15) as i32);
+    (value * factor).round() / factor
+}
+
 fn format_duration(d: Duration) -> String {
     if d.as_secs() > 0 {
         format!("{:.2} s", d.as_secs_f64())
@@ -273,18 +699,144 @@ mod tests {
         assert!(cmd.execute().is_ok());
     }
 
+    #[test]
+    fn test_csv_precision() {
+        let results = vec![BenchmarkResult::new(
+            "Fast",
+            3,
+            Duration::from_nanos(1_234_567),
+            false,
+        )];
+        let total_time_column = |csv: &str| {
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .split(',')
+                .nth(2)
+                .unwrap()
+                .to_string()
+        };
+
+        let default = BenchmarkCommand::new(3, "csv".to_string(), false);
+        assert_eq!(total_time_column(&default.format_csv(&results)), "0.001235");
+
+        let precise = BenchmarkCommand::new(3, "csv".to_string(), false).with_precision(Some(9));
+        assert_eq!(
+            total_time_column(&precise.format_csv(&results)),
+            "0.001234567"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&precise.with_precision(Some(2)).format_json(&results)).unwrap();
+        assert_eq!(json["benchmarks"][0]["total_time_ms"], 1.23);
+    }
+
+    #[test]
+    fn test_yaml_output_matches_json_envelope() {
+        let results = vec![BenchmarkResult::new(
+            "Fast",
+            3,
+            Duration::from_millis(3),
+            false,
+        )];
+        let cmd = BenchmarkCommand::new(3, "yaml".to_string(), false);
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&cmd.format_yaml(&results)).unwrap();
+        let benchmarks = yaml["benchmarks"].as_sequence().unwrap();
+        assert_eq!(benchmarks.len(), 1);
+        assert_eq!(benchmarks[0]["name"].as_str(), Some("Fast"));
+        assert_eq!(benchmarks[0]["iterations"].as_u64(), Some(3));
+        assert!(yaml["timestamp"].is_string());
+    }
+
+    #[test]
+    fn test_locale_groups_console_numbers() {
+        let plain = BenchmarkCommand::new(3, "console".to_string(), false);
+        assert_eq!(plain.format_number(1234567.891), "1234567.89");
+
+        let german = plain.with_locale(Some(format::locale("de").unwrap()));
+        assert_eq!(german.format_number(1234567.891), "1.234.567,89");
+    }
+
+    #[test]
+    fn test_grep_benchmark() {
+        let cmd = BenchmarkCommand::new(3, "console".to_string(), false);
+        let result = cmd.benchmark_grep();
+
+        assert_eq!(result.iterations, 3);
+        assert!(result.ops_per_sec > 0.0);
+        assert!(result.lines_per_sec.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_buffer_size_benchmarks() {
+        let cmd = BenchmarkCommand::new(2, "console".to_string(), false);
+        for buffer_size in [4 * 1024, 1024 * 1024] {
+            let result = cmd.benchmark_buffer_size("Checksum", buffer_size);
+            assert_eq!(result.iterations, 2);
+            assert!(result.throughput_mb_s.unwrap() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_logger_benchmark_both_modes() {
+        let cmd = BenchmarkCommand::new(2, "console".to_string(), false);
+        for use_colors in [false, true] {
+            let result = cmd.benchmark_logger(use_colors);
+            assert_eq!(result.iterations, 2);
+            assert!(result.lines_per_sec.unwrap() > 0.0);
+        }
+    }
+
     #[test]
     fn test_verbose_mode() {
         let cmd = BenchmarkCommand::new(10, "console".to_string(), true);
         assert!(cmd.execute().is_ok());
     }
 
+    #[test]
+    fn test_smoke_mode() {
+        let cmd = BenchmarkCommand::new(1_000_000, "console".to_string(), false).with_smoke(true);
+        let start = Instant::now();
+        let results = cmd.run_smoke();
+
+        assert_eq!(results.len(), BENCHMARKS.len());
+        assert!(results.iter().all(|r| r.passed));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(cmd.execute().is_ok());
+    }
+
+    #[test]
+    fn test_checksum_throughput() {
+        let cmd = BenchmarkCommand::new(10, "console".to_string(), false);
+
+        for algorithm in ["md5", "sha1", "sha256", "sha512"] {
+            let result = cmd.benchmark_checksum(algorithm);
+            assert!(result.name.contains(&algorithm.to_uppercase()));
+            assert!(result.throughput_mb_s.unwrap() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_timeout_returns_partial_results() {
+        let cmd = BenchmarkCommand::new(usize::MAX, "console".to_string(), false)
+            .with_timeout(Duration::from_millis(50));
+
+        let start = Instant::now();
+        let results = cmd.run_benchmarks();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(results.len(), BENCHMARKS.len());
+        assert!(results.iter().all(|r| r.timed_out));
+        assert!(results.iter().all(|r| r.iterations < usize::MAX));
+    }
+
     #[test]
     fn test_benchmark_results_structure() {
         let cmd = BenchmarkCommand::new(10, "console".to_string(), false);
         let results = cmd.run_benchmarks();
 
-        assert_eq!(results.len(), 5);
+        assert_eq!(results.len(), BENCHMARKS.len());
         for result in results {
             assert!(result.iterations == 10);
             assert!(result.ops_per_sec > 0.0);
@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use colored::*;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::utils::file_handler::{FileError, FileHandler};
+
+/// Deltas within this many percent are treated as noise and left uncolored.
+const NOISE_THRESHOLD_PERCENT: f64 = 5.0;
+
+pub struct BenchmarkCompareCommand {
+    baseline: PathBuf,
+    candidate: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ComparisonRow {
+    name: String,
+    baseline: Option<f64>,
+    candidate: Option<f64>,
+}
+
+impl ComparisonRow {
+    /// Percent change in ops/sec from baseline to candidate; positive is faster.
+    fn delta_percent(&self) -> Option<f64> {
+        match (self.baseline, self.candidate) {
+            (Some(base), Some(candidate)) if base > 0.0 => Some((candidate - base) / base * 100.0),
+            _ => None,
+        }
+    }
+}
+
+impl BenchmarkCompareCommand {
+    pub fn new(baseline: PathBuf, candidate: PathBuf) -> Self {
+        Self {
+            baseline,
+            candidate,
+        }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let rows = compare(
+            &load_results(&self.baseline)?,
+            &load_results(&self.candidate)?,
+        );
+        let (baseline_label, candidate_label) = (label(&self.baseline), label(&self.candidate));
+
+        println!(
+            "{:<24} {:>14} {:>14} {:>10}",
+            "Benchmark", baseline_label, candidate_label, "Delta"
+        );
+        println!("{}", "-".repeat(65));
+        for row in &rows {
+            let delta = match (row.delta_percent(), row.baseline, row.candidate) {
+                (Some(delta), _, _) => color_delta(delta),
+                (None, Some(_), None) => format!("only in {}", baseline_label).yellow(),
+                (None, None, Some(_)) => format!("only in {}", candidate_label).yellow(),
+                _ => "n/a".normal(),
+            };
+            println!(
+                "{:<24} {:>14} {:>14} {:>10}",
+                row.name,
+                format_ops(row.baseline),
+                format_ops(row.candidate),
+                delta
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Benchmarks that slowed down by more than the noise threshold relative to the
+/// `baseline` results file, with their (negative) percent change.
+pub fn regressions(baseline: &Path, candidate: &[(String, f64)]) -> Result<Vec<(String, f64)>> {
+    Ok(compare(&load_results(baseline)?, candidate)
+        .into_iter()
+        .filter_map(|row| {
+            let delta = row.delta_percent()?;
+            (delta <= -NOISE_THRESHOLD_PERCENT).then_some((row.name, delta))
+        })
+        .collect())
+}
+
+/// Reads `name -> ops_per_second` pairs from a `benchmark --output json` file, in file order.
+fn load_results(path: &Path) -> Result<Vec<(String, f64)>> {
+    let data: Value = FileHandler::read_json(path)?;
+    let Some(benchmarks) = data["benchmarks"].as_array() else {
+        bail!(FileError::InvalidJson(format!(
+            "{}: missing \"benchmarks\" array",
+            path.display()
+        )));
+    };
+
+    Ok(benchmarks
+        .iter()
+        .filter_map(|b| {
+            Some((
+                b["name"].as_str()?.to_string(),
+                b["ops_per_second"].as_f64()?,
+            ))
+        })
+        .collect())
+}
+
+/// Pairs benchmarks by name: baseline order first, then any only in the candidate.
+fn compare(baseline: &[(String, f64)], candidate: &[(String, f64)]) -> Vec<ComparisonRow> {
+    let find = |results: &[(String, f64)], name: &str| {
+        results.iter().find(|(n, _)| n == name).map(|(_, ops)| *ops)
+    };
+
+    let mut rows: Vec<ComparisonRow> = baseline
+        .iter()
+        .map(|(name, ops)| ComparisonRow {
+            name: name.clone(),
+            baseline: Some(*ops),
+            candidate: find(candidate, name),
+        })
+        .collect();
+    rows.extend(
+        candidate
+            .iter()
+            .filter(|(name, _)| find(baseline, name).is_none())
+            .map(|(name, ops)| ComparisonRow {
+                name: name.clone(),
+                baseline: None,
+                candidate: Some(*ops),
+            }),
+    );
+    rows
+}
+
+fn color_delta(delta: f64) -> ColoredString {
+    let text = format!("{:+.1}%", delta);
+    if delta >= NOISE_THRESHOLD_PERCENT {
+        text.green()
+    } else if delta <= -NOISE_THRESHOLD_PERCENT {
+        text.red()
+    } else {
+        text.normal()
+    }
+}
+
+fn format_ops(ops: Option<f64>) -> String {
+    ops.map_or_else(|| "-".to_string(), |ops| format!("{:.2}", ops))
+}
+
+fn label(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_results(path: &Path, benchmarks: &[(&str, f64)]) {
+        let benchmarks: Vec<Value> = benchmarks
+            .iter()
+            .map(|(name, ops)| serde_json::json!({"name": name, "ops_per_second": ops}))
+            .collect();
+        FileHandler::write_json(
+            path,
+            &serde_json::json!({ "benchmarks": benchmarks }),
+            false,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_deltas_for_shared_benchmarks() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.json");
+        let b = dir.path().join("b.json");
+        write_results(&a, &[("Parse", 1000.0), ("Hash", 400.0), ("Old", 10.0)]);
+        write_results(&b, &[("Hash", 300.0), ("Parse", 1250.0), ("New", 5.0)]);
+
+        let rows = compare(&load_results(&a).unwrap(), &load_results(&b).unwrap());
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Parse", "Hash", "Old", "New"]);
+
+        assert_eq!(rows[0].delta_percent(), Some(25.0));
+        assert_eq!(rows[1].delta_percent(), Some(-25.0));
+        assert_eq!((rows[2].baseline, rows[2].candidate), (Some(10.0), None));
+        assert_eq!((rows[3].baseline, rows[3].candidate), (None, Some(5.0)));
+        assert_eq!(rows[3].delta_percent(), None);
+
+        assert!(BenchmarkCompareCommand::new(a, b).execute().is_ok());
+    }
+
+    #[test]
+    fn test_regressions_beyond_noise() {
+        let dir = TempDir::new().unwrap();
+        let baseline = dir.path().join("baseline.json");
+        write_results(&baseline, &[("Parse", 1000.0), ("Hash", 400.0)]);
+
+        let candidate = vec![
+            ("Parse".to_string(), 980.0),
+            ("Hash".to_string(), 200.0),
+            ("New".to_string(), 1.0),
+        ];
+        assert_eq!(
+            regressions(&baseline, &candidate).unwrap(),
+            vec![("Hash".to_string(), -50.0)]
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_benchmark_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("other.json");
+        FileHandler::write(&path, r#"{"name": "x"}"#).unwrap();
+
+        assert!(load_results(&path).is_err());
+    }
+}
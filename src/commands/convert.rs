@@ -0,0 +1,550 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::utils::file_handler::{FileError, FileHandler, LineOptions};
+use crate::utils::json;
+use crate::utils::parallel;
+
+const DEFAULT_INDENT: &str = "  ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl DataFormat {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Ok(DataFormat::Json),
+            "yaml" | "yml" => Ok(DataFormat::Yaml),
+            "csv" => Ok(DataFormat::Csv),
+            _ => bail!(FileError::UnsupportedFormat(name.to_string())),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        Self::from_name(extension)
+            .map_err(|_| FileError::UnsupportedFormat(path.display().to_string()).into())
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DataFormat::Json => "json",
+            DataFormat::Yaml => "yaml",
+            DataFormat::Csv => "csv",
+        }
+    }
+}
+
+pub struct ConvertCommand {
+    input: PathBuf,
+    from: Option<DataFormat>,
+    to: DataFormat,
+    output: Option<PathBuf>,
+    indent: String,
+    dry_run: bool,
+    continue_on_error: bool,
+    infer_types: bool,
+    canonical: bool,
+    jobs: usize,
+}
+
+impl ConvertCommand {
+    /// `input` may be a glob such as `configs/*.yml`, converting every match.
+    pub fn new(input: PathBuf, to: DataFormat, output: Option<PathBuf>) -> Self {
+        Self {
+            input,
+            from: None,
+            to,
+            output,
+            indent: DEFAULT_INDENT.to_string(),
+            dry_run: false,
+            continue_on_error: false,
+            infer_types: false,
+            canonical: false,
+            jobs: 1,
+        }
+    }
+
+    /// Overrides the source format otherwise detected from each file's extension.
+    pub fn with_from(mut self, from: Option<DataFormat>) -> Self {
+        self.from = from;
+        self
+    }
+
+    /// Indent unit for JSON output; YAML output always uses its own layout.
+    pub fn with_indent(mut self, indent: Option<String>) -> Self {
+        if let Some(indent) = indent {
+            self.indent = indent;
+        }
+        self
+    }
+
+    /// Prints the planned conversion instead of writing anything.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Exits successfully from a batch even when some files failed to convert.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Turns CSV cells that look like numbers or booleans into JSON numbers and
+    /// booleans; otherwise every CSV cell becomes a string.
+    pub fn with_infer_types(mut self, infer_types: bool) -> Self {
+        self.infer_types = infer_types;
+        self
+    }
+
+    /// Writes JSON output in RFC 8785 canonical form; only valid with JSON output.
+    pub fn with_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Converts up to `jobs` batch files at once (0 = one per CPU); results are
+    /// still reported in input order.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        if self.canonical && self.to != DataFormat::Json {
+            bail!("--canonical requires --to json");
+        }
+        let inputs = self.inputs()?;
+
+        if self.dry_run {
+            for input in &inputs {
+                println!("{}", self.describe_plan(input)?);
+            }
+            return Ok(());
+        }
+
+        let mut outcomes = self.convert_all(&inputs);
+        if outcomes.len() == 1 {
+            let (input, outcome) = outcomes.remove(0);
+            println!("{} -> {}", input.display(), outcome?.display());
+            return Ok(());
+        }
+
+        let mut failed = 0;
+        for (input, outcome) in &outcomes {
+            match outcome {
+                Ok(output) => println!("{} -> {}", input.display(), output.display()),
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("FAILED {}: {}", input.display(), e);
+                }
+            }
+        }
+        println!(
+            "Converted {} files: {} succeeded, {} failed",
+            outcomes.len(),
+            outcomes.len() - failed,
+            failed
+        );
+
+        if failed > 0 && !self.continue_on_error {
+            bail!("{} of {} files failed to convert", failed, outcomes.len());
+        }
+        Ok(())
+    }
+
+    /// Expands a glob input into its sorted matches; a plain path is used as is.
+    fn inputs(&self) -> Result<Vec<PathBuf>> {
+        let pattern = self.input.to_string_lossy();
+        if !pattern.contains(['*', '?', '[']) {
+            return Ok(vec![self.input.clone()]);
+        }
+
+        let matches = FileHandler::glob(&pattern)?;
+        if matches.is_empty() {
+            bail!(FileError::NotFound(format!("no files match {}", pattern)));
+        }
+        if matches.len() > 1 && self.output.is_some() {
+            bail!("--output cannot be used when converting multiple files");
+        }
+        Ok(matches)
+    }
+
+    fn convert_all(&self, inputs: &[PathBuf]) -> Vec<(PathBuf, Result<PathBuf>)> {
+        parallel::map_ordered(inputs, self.jobs, |input| {
+            (input.clone(), self.convert_file(input))
+        })
+    }
+
+    /// Detects the source format and target path without touching the filesystem.
+    pub fn plan(&self, input: &Path) -> Result<(DataFormat, PathBuf)> {
+        if !input.exists() {
+            bail!(FileError::NotFound(input.display().to_string()));
+        }
+
+        let from = match self.from {
+            Some(from) => from,
+            None => DataFormat::from_path(input)?,
+        };
+        let output = self
+            .output
+            .clone()
+            .unwrap_or_else(|| input.with_extension(self.to.extension()));
+        if is_same_file(input, &output) {
+            bail!(
+                "Output {} would overwrite the input; choose another path with -o",
+                output.display()
+            );
+        }
+        Ok((from, output))
+    }
+
+    fn describe_plan(&self, input: &Path) -> Result<String> {
+        let (from, output) = self.plan(input)?;
+        Ok(format!(
+            "{} ({}) -> {} ({})",
+            input.display(),
+            from.extension(),
+            output.display(),
+            self.to.extension()
+        ))
+    }
+
+    /// Converts the (non-glob) input and returns where the output went.
+    pub fn convert(&self) -> Result<PathBuf> {
+        self.convert_file(&self.input)
+    }
+
+    /// Writes the converted document atomically and returns where it went.
+    fn convert_file(&self, input: &Path) -> Result<PathBuf> {
+        let (from, output) = self.plan(input)?;
+
+        let content = if self.canonical {
+            let value = read_document(input, from, self.infer_types)?;
+            json::to_canonical_string(&value)? + "\n"
+        } else {
+            convert_document(input, from, self.to, &self.indent, self.infer_types)?
+        };
+        FileHandler::atomic_write(&output, &content)?;
+
+        Ok(output)
+    }
+}
+
+fn is_same_file(input: &Path, output: &Path) -> bool {
+    input == output
+        || match (input.canonicalize(), output.canonicalize()) {
+            (Ok(input), Ok(output)) => input == output,
+            _ => false,
+        }
+}
+
+/// Reads `input` as `from` and re-serializes it as `to`, indenting JSON output with `indent`.
+/// CSV input becomes an array of objects (see `infer_types`); CSV output needs
+/// an array of objects, flattened into one row each.
+pub fn convert_document(
+    input: &Path,
+    from: DataFormat,
+    to: DataFormat,
+    indent: &str,
+    infer_types: bool,
+) -> Result<String> {
+    let value = read_document(input, from, infer_types)?;
+
+    Ok(match to {
+        DataFormat::Json => json::to_string_indented(&value, indent)? + "\n",
+        DataFormat::Yaml => serde_yaml::to_string(&value)?,
+        DataFormat::Csv => to_csv(input, &value)?,
+    })
+}
+
+fn read_document(input: &Path, from: DataFormat, infer_types: bool) -> Result<Value> {
+    match from {
+        DataFormat::Json => FileHandler::read_json(input),
+        DataFormat::Yaml => FileHandler::read_yaml(input),
+        DataFormat::Csv => from_csv(input, infer_types),
+    }
+}
+
+fn from_csv(input: &Path, infer_types: bool) -> Result<Value> {
+    let mut rows = Vec::new();
+    FileHandler::for_each_csv_record(input, &LineOptions::default(), |record| {
+        let row = record
+            .into_iter()
+            .map(|(column, cell)| {
+                let value = if infer_types {
+                    infer_cell(cell)
+                } else {
+                    Value::String(cell)
+                };
+                (column, value)
+            })
+            .collect();
+        rows.push(Value::Object(row));
+        Ok(())
+    })?;
+    Ok(Value::Array(rows))
+}
+
+/// `true`/`false` become booleans and integers or finite decimals numbers;
+/// anything else, including empty cells and zero-padded codes such as `007`
+/// or ZIP codes, stays a string.
+fn infer_cell(cell: String) -> Value {
+    match cell.as_str() {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    let digits = cell.strip_prefix(['-', '+']).unwrap_or(&cell);
+    if digits.len() > 1 && digits.starts_with('0') && !digits[1..].starts_with('.') {
+        return Value::String(cell);
+    }
+    if let Ok(integer) = cell.parse::<i64>() {
+        return integer.into();
+    }
+    match cell
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+    {
+        Some(number) => Value::Number(number),
+        None => Value::String(cell),
+    }
+}
+
+/// One row per object with the sorted union of flattened keys as columns;
+/// missing keys become empty cells.
+fn to_csv(input: &Path, value: &Value) -> Result<String> {
+    let rows: Vec<_> = match value.as_array() {
+        Some(items) if items.iter().all(Value::is_object) => {
+            items.iter().map(json::flatten).collect()
+        }
+        _ => bail!(FileError::InvalidJson(format!(
+            "{}: CSV output needs an array of objects",
+            input.display()
+        ))),
+    };
+    let columns: BTreeSet<&String> = rows.iter().flat_map(|row| row.keys()).collect();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&columns)?;
+    for row in &rows {
+        writer.write_record(columns.iter().map(|column| match row.get(*column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        }))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_yaml_to_sibling_json() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("config.yml");
+        FileHandler::write(&input, "name: demo\nports:\n  - 80\n  - 443\n").unwrap();
+
+        let output = ConvertCommand::new(input, DataFormat::Json, None)
+            .convert()
+            .unwrap();
+
+        assert_eq!(output, dir.path().join("config.json"));
+        let value: Value = FileHandler::read_json(&output).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"name": "demo", "ports": [80, 443]})
+        );
+    }
+
+    #[test]
+    fn test_indent_option() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("config.yml");
+        FileHandler::write(&input, "a: 1\n").unwrap();
+
+        let output = ConvertCommand::new(input, DataFormat::Json, None)
+            .with_indent(Some("    ".to_string()))
+            .convert()
+            .unwrap();
+        assert_eq!(FileHandler::read(output).unwrap(), "{\n    \"a\": 1\n}\n");
+    }
+
+    #[test]
+    fn test_canonical_yaml_and_json_match() {
+        let dir = TempDir::new().unwrap();
+        let yaml = dir.path().join("a.yml");
+        let json = dir.path().join("b.json");
+        FileHandler::write(&yaml, "ratio: 0.50\nname: demo\n").unwrap();
+        FileHandler::write(&json, r#"{ "name": "demo", "ratio": 5e-1 }"#).unwrap();
+
+        let canonical = |input: PathBuf| {
+            let output = input.with_extension("canonical.json");
+            ConvertCommand::new(input, DataFormat::Json, Some(output))
+                .with_canonical(true)
+                .convert()
+                .map(|path| FileHandler::read(path).unwrap())
+                .unwrap()
+        };
+        assert_eq!(canonical(yaml), "{\"name\":\"demo\",\"ratio\":0.5}\n");
+        assert_eq!(canonical(json), "{\"name\":\"demo\",\"ratio\":0.5}\n");
+
+        let to_yaml = ConvertCommand::new(dir.path().join("a.yml"), DataFormat::Yaml, None)
+            .with_canonical(true);
+        assert!(to_yaml.execute().is_err());
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("config.json");
+        FileHandler::write(&input, r#"{"a": 1}"#).unwrap();
+
+        let cmd = ConvertCommand::new(input.clone(), DataFormat::Yaml, None).with_dry_run(true);
+        assert_eq!(
+            cmd.describe_plan(&input).unwrap(),
+            format!(
+                "{} (json) -> {} (yaml)",
+                input.display(),
+                dir.path().join("config.yaml").display()
+            )
+        );
+        cmd.execute().unwrap();
+
+        let entries = FileHandler::walk_dir(dir.path(), &Default::default()).unwrap();
+        assert_eq!(entries, vec![input]);
+    }
+
+    #[test]
+    fn test_glob_batch() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.yml"), "name: a\n").unwrap();
+        FileHandler::write(dir.path().join("b.yml"), "name: b\n").unwrap();
+        FileHandler::write(dir.path().join("skip.txt"), "text").unwrap();
+
+        let pattern = dir.path().join("*.yml");
+        let cmd = ConvertCommand::new(pattern, DataFormat::Json, None);
+        cmd.execute().unwrap();
+
+        for name in ["a", "b"] {
+            let value: Value =
+                FileHandler::read_json(dir.path().join(format!("{}.json", name))).unwrap();
+            assert_eq!(value["name"], name);
+        }
+    }
+
+    #[test]
+    fn test_glob_batch_failures() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("good.yml"), "ok: true\n").unwrap();
+        FileHandler::write(dir.path().join("bad.yml"), "key: [unclosed\n").unwrap();
+
+        let pattern = dir.path().join("*.yml");
+        let cmd = ConvertCommand::new(pattern.clone(), DataFormat::Json, None);
+        let outcomes = cmd.convert_all(&cmd.inputs().unwrap());
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes[0].1.is_err());
+        assert!(outcomes[1].1.is_ok());
+        assert!(cmd.execute().is_err());
+
+        let lenient =
+            ConvertCommand::new(pattern, DataFormat::Json, None).with_continue_on_error(true);
+        assert!(lenient.execute().is_ok());
+    }
+
+    #[test]
+    fn test_json_array_to_csv() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("rows.json");
+        FileHandler::write(&input, r#"[{"a":1},{"a":2,"b":3}]"#).unwrap();
+
+        let output = ConvertCommand::new(input, DataFormat::Csv, None)
+            .convert()
+            .unwrap();
+        assert_eq!(output, dir.path().join("rows.csv"));
+        assert_eq!(FileHandler::read(&output).unwrap(), "a,b\n1,\n2,3\n");
+
+        let nested = dir.path().join("nested.json");
+        FileHandler::write(&nested, r#"[{"user":{"name":"x, y"},"tags":["t"]}]"#).unwrap();
+        let content =
+            convert_document(&nested, DataFormat::Json, DataFormat::Csv, "", false).unwrap();
+        assert_eq!(content, "tags.0,user.name\nt,\"x, y\"\n");
+
+        FileHandler::write(&nested, r#"{"not": "an array"}"#).unwrap();
+        assert!(convert_document(&nested, DataFormat::Json, DataFormat::Csv, "", false).is_err());
+    }
+
+    #[test]
+    fn test_csv_to_json_infer_types() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("items.csv");
+        FileHandler::write(
+            &input,
+            "id,active,price,name,zip,ratio\n42,true,9.5,0x1f,02134,0.25\n",
+        )
+        .unwrap();
+
+        let typed = ConvertCommand::new(input.clone(), DataFormat::Json, None)
+            .with_infer_types(true)
+            .convert()
+            .unwrap();
+        let value: Value = FileHandler::read_json(&typed).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "id": 42,
+                "active": true,
+                "price": 9.5,
+                "name": "0x1f",
+                "zip": "02134",
+                "ratio": 0.25
+            }])
+        );
+
+        let content =
+            convert_document(&input, DataFormat::Csv, DataFormat::Json, "  ", false).unwrap();
+        let value: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value[0]["id"], "42");
+        assert_eq!(value[0]["active"], "true");
+    }
+
+    #[test]
+    fn test_same_format_does_not_overwrite_input() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("x.json");
+        FileHandler::write(&input, r#"{"a": 1}"#).unwrap();
+
+        let err = ConvertCommand::new(input.clone(), DataFormat::Json, None)
+            .convert()
+            .unwrap_err();
+        assert!(err.to_string().contains("would overwrite the input"));
+
+        let explicit = ConvertCommand::new(
+            input.clone(),
+            DataFormat::Yaml,
+            Some(dir.path().join(".").join("x.json")),
+        );
+        assert!(explicit.convert().is_err());
+        assert_eq!(FileHandler::read(&input).unwrap(), r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn test_unknown_format_is_rejected() {
+        let err = DataFormat::from_name("toml").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FileError>(),
+            Some(FileError::UnsupportedFormat(_))
+        ));
+    }
+}
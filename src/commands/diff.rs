@@ -0,0 +1,69 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::utils::file_handler::FileHandler;
+use crate::utils::format;
+
+pub struct DiffCommand {
+    old: PathBuf,
+    new: PathBuf,
+    context: usize,
+}
+
+impl DiffCommand {
+    pub fn new(old: PathBuf, new: PathBuf, context: usize) -> Self {
+        Self { old, new, context }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        print!("{}", self.render(atty::is(atty::Stream::Stdout))?);
+        Ok(())
+    }
+
+    /// The full unified diff, or an empty string when the files match.
+    fn render(&self, color: bool) -> Result<String> {
+        let lines = FileHandler::diff(&self.old, &self.new)?;
+        let hunks = format::render_diff(&lines, self.context, color);
+        if hunks.is_empty() {
+            return Ok(hunks);
+        }
+        Ok(format!(
+            "--- {}\n+++ {}\n{}",
+            self.old.display(),
+            self.new.display(),
+            hunks
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_renders_headers_and_hunks() {
+        let dir = TempDir::new().unwrap();
+        let old = dir.path().join("a.conf");
+        let new = dir.path().join("b.conf");
+        FileHandler::write(&old, "port=80\nhost=a\n").unwrap();
+        FileHandler::write(&new, "port=8080\nhost=a\n").unwrap();
+
+        let output = DiffCommand::new(old.clone(), new.clone(), 0)
+            .render(false)
+            .unwrap();
+        assert_eq!(
+            output,
+            format!(
+                "--- {}\n+++ {}\n@@ -1,1 +1,1 @@\n-port=80\n+port=8080\n",
+                old.display(),
+                new.display()
+            )
+        );
+
+        assert_eq!(
+            DiffCommand::new(old.clone(), old, 3).render(false).unwrap(),
+            ""
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use serde_json::json;
+
+use crate::commands::version::VersionCommand;
+
+pub struct EnvCommand {
+    json_output: bool,
+}
+
+impl EnvCommand {
+    pub fn new(json_output: bool) -> Self {
+        Self { json_output }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let info = self.build_env_info();
+
+        if self.json_output {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+        } else {
+            for (key, value) in info.as_object().into_iter().flatten() {
+                match value {
+                    serde_json::Value::String(s) => println!("{}: {}", key, s),
+                    serde_json::Value::Null => println!("{}: (none)", key),
+                    other => println!("{}: {}", key, other),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_env_info(&self) -> serde_json::Value {
+        let version_info = VersionCommand::new(false, false).build_version_info();
+
+        json!({
+            "name": version_info["name"],
+            "version": version_info["version"],
+            "build_date": version_info["build_date"],
+            "rust_version": version_info["ruby_version"],
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "family": std::env::consts::FAMILY,
+            "cpu_cores": std::thread::available_parallelism().map_or(1, |n| n.get()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_info_structure() {
+        let cmd = EnvCommand::new(true);
+        let info = cmd.build_env_info();
+
+        assert_eq!(info["os"], std::env::consts::OS);
+        assert_eq!(info["arch"], std::env::consts::ARCH);
+        assert_eq!(info["version"], VersionCommand::VERSION);
+        assert!(info["cpu_cores"].as_u64().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_execute_outputs() {
+        assert!(EnvCommand::new(true).execute().is_ok());
+        assert!(EnvCommand::new(false).execute().is_ok());
+    }
+}
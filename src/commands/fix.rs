@@ -0,0 +1,59 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::utils::file_handler::FileHandler;
+
+pub struct FixCommand {
+    files: Vec<PathBuf>,
+    trim_trailing: bool,
+    ensure_final_newline: bool,
+}
+
+impl FixCommand {
+    pub fn new(files: Vec<PathBuf>, trim_trailing: bool, ensure_final_newline: bool) -> Self {
+        Self {
+            files,
+            trim_trailing,
+            ensure_final_newline,
+        }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let fixed = self.fix()?;
+        for file in &fixed {
+            println!("Fixed {}", file.display());
+        }
+        println!("{} of {} files changed", fixed.len(), self.files.len());
+        Ok(())
+    }
+
+    /// Fixes each file in place and returns the ones that changed.
+    fn fix(&self) -> Result<Vec<PathBuf>> {
+        let mut fixed = Vec::new();
+        for file in &self.files {
+            if FileHandler::fix_whitespace(file, self.trim_trailing, self.ensure_final_newline)? {
+                fixed.push(file.clone());
+            }
+        }
+        Ok(fixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reports_only_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let messy = dir.path().join("messy.rs");
+        let clean = dir.path().join("clean.rs");
+        FileHandler::write(&messy, "fn main() {}   ").unwrap();
+        FileHandler::write(&clean, "fn main() {}\n").unwrap();
+
+        let cmd = FixCommand::new(vec![messy.clone(), clean], true, true);
+        assert_eq!(cmd.fix().unwrap(), vec![messy.clone()]);
+        assert_eq!(FileHandler::read(&messy).unwrap(), "fn main() {}\n");
+    }
+}
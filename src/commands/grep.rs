@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use regex::RegexBuilder;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::utils::file_handler::{FileHandler, GrepOptions};
+
+pub struct GrepCommand {
+    pattern: String,
+    files: Vec<PathBuf>,
+    count: bool,
+    invert_match: bool,
+    ignore_case: bool,
+    before: usize,
+    after: usize,
+}
+
+impl GrepCommand {
+    pub fn new(pattern: String, files: Vec<PathBuf>) -> Self {
+        Self {
+            pattern,
+            files,
+            count: false,
+            invert_match: false,
+            ignore_case: false,
+            before: 0,
+            after: 0,
+        }
+    }
+
+    /// Reports only the number of matching lines per file, like `grep -c`.
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Selects the lines that do not match, like `grep -v`.
+    pub fn with_invert_match(mut self, invert_match: bool) -> Self {
+        self.invert_match = invert_match;
+        self
+    }
+
+    /// Matches letters regardless of case, like `grep -i`.
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Shows this many lines before and after each match, like `grep -B`/`-A`.
+    /// Ignored when counting.
+    pub fn with_context(mut self, before: usize, after: usize) -> Self {
+        self.before = before;
+        self.after = after;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let mut output = BufWriter::new(io::stdout().lock());
+        self.write_to(&mut output)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// Writes output lines in file order as they are found, holding at most the
+    /// `before` context window in memory. With several files each line is
+    /// prefixed by `file:` (`file-` for context lines), and `--` separates
+    /// non-adjacent context groups.
+    fn write_to<W: Write>(&self, output: &mut W) -> Result<()> {
+        let pattern = RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .with_context(|| format!("Invalid pattern '{}'", self.pattern))?;
+        let options = GrepOptions {
+            invert_match: self.invert_match,
+            ..Default::default()
+        };
+        let prefixed = self.files.len() > 1;
+
+        let with_context = self.before > 0 || self.after > 0;
+        let mut wrote_group = false;
+        for file in &self.files {
+            let prefix = if prefixed {
+                format!("{}:", file.display())
+            } else {
+                String::new()
+            };
+
+            if with_context && !self.count {
+                FileHandler::grep_context_each(
+                    file,
+                    &pattern,
+                    &options,
+                    self.before,
+                    self.after,
+                    |entry, new_group| {
+                        if new_group && wrote_group {
+                            writeln!(output, "--")?;
+                        }
+                        wrote_group = true;
+                        let prefix = match (prefixed, entry.is_match) {
+                            (false, _) => String::new(),
+                            (true, true) => format!("{}:", file.display()),
+                            (true, false) => format!("{}-", file.display()),
+                        };
+                        writeln!(output, "{}{}", prefix, entry.line)?;
+                        Ok(())
+                    },
+                )?;
+            } else if self.count {
+                let count = FileHandler::grep_count(file, &pattern, &options)?;
+                writeln!(output, "{}{}", prefix, count)?;
+            } else {
+                FileHandler::grep_each(file, &pattern, &options, |found| {
+                    writeln!(output, "{}{}", prefix, found.line)?;
+                    Ok(())
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn lines(command: &GrepCommand) -> Result<Vec<String>> {
+        let mut output = Vec::new();
+        command.write_to(&mut output)?;
+        Ok(String::from_utf8(output)?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    #[test]
+    fn test_count_per_file() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app.log");
+        let db = dir.path().join("db.log");
+        FileHandler::write(&app, "ERROR a\nok\nERROR b\nERROR c\n").unwrap();
+        FileHandler::write(&db, "ok\n").unwrap();
+
+        let single = GrepCommand::new("ERROR".to_string(), vec![app.clone()]).with_count(true);
+        assert_eq!(lines(&single).unwrap(), vec!["3"]);
+
+        let both =
+            GrepCommand::new("ERROR".to_string(), vec![app.clone(), db.clone()]).with_count(true);
+        assert_eq!(
+            lines(&both).unwrap(),
+            vec![
+                format!("{}:3", app.display()),
+                format!("{}:0", db.display())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matching_lines() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app.log");
+        FileHandler::write(&app, "ERROR a\nok\nERROR b\n").unwrap();
+
+        let command = GrepCommand::new(r"ERROR \w".to_string(), vec![app]);
+        assert_eq!(lines(&command).unwrap(), vec!["ERROR a", "ERROR b"]);
+        assert!(lines(&GrepCommand::new("(".to_string(), vec![])).is_err());
+    }
+
+    #[test]
+    fn test_context_around_single_match() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app.log");
+        FileHandler::write(&app, "one\ntwo\nERROR three\nfour\nfive\n").unwrap();
+
+        let command = GrepCommand::new("ERROR".to_string(), vec![app]).with_context(1, 1);
+        assert_eq!(lines(&command).unwrap(), vec!["two", "ERROR three", "four"]);
+    }
+
+    #[test]
+    fn test_context_separates_groups() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app.log");
+        FileHandler::write(&app, "a\nERROR b\nc\nd\ne\nERROR f\n").unwrap();
+
+        let command = GrepCommand::new("ERROR".to_string(), vec![app]).with_context(0, 1);
+        assert_eq!(
+            lines(&command).unwrap(),
+            vec!["ERROR b", "c", "--", "ERROR f"]
+        );
+    }
+
+    #[test]
+    fn test_invert_and_ignore_case() {
+        let dir = TempDir::new().unwrap();
+        let app = dir.path().join("app.log");
+        FileHandler::write(&app, "ERROR a\nok\nerror b\nwarn\n").unwrap();
+
+        let inverted =
+            GrepCommand::new("ERROR".to_string(), vec![app.clone()]).with_invert_match(true);
+        assert_eq!(lines(&inverted).unwrap(), vec!["ok", "error b", "warn"]);
+
+        let both = GrepCommand::new("error".to_string(), vec![app])
+            .with_invert_match(true)
+            .with_ignore_case(true);
+        assert_eq!(lines(&both).unwrap(), vec!["ok", "warn"]);
+    }
+}
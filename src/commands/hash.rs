@@ -0,0 +1,147 @@
+use anyhow::{bail, Result};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::utils::file_handler::FileHandler;
+use crate::utils::parallel;
+
+const STDIN_PATH: &str = "-";
+
+pub struct HashCommand {
+    files: Vec<PathBuf>,
+    algorithm: String,
+    jobs: usize,
+}
+
+impl HashCommand {
+    pub fn new(files: Vec<PathBuf>, algorithm: String) -> Self {
+        Self {
+            files,
+            algorithm,
+            jobs: 1,
+        }
+    }
+
+    /// Hashes up to `jobs` files at once (0 = one per CPU); output keeps argument order.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let mut digests = self.digest_all();
+        if digests.len() == 1 {
+            let (file, digest) = digests.remove(0);
+            println!("{}  {}", digest?, file.display());
+            return Ok(());
+        }
+
+        let mut failed = 0;
+        for (file, digest) in &digests {
+            match digest {
+                // Same `<hash>  <name>` layout as sha256sum, so output can feed `verify`.
+                Ok(digest) => println!("{}  {}", digest, file.display()),
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("FAILED {}: {}", file.display(), e);
+                }
+            }
+        }
+
+        if failed > 0 {
+            bail!("{} of {} files could not be hashed", failed, digests.len());
+        }
+        Ok(())
+    }
+
+    /// Hashes every file, carrying on past failures. Stdin is only locked while
+    /// it is being read, so regular files never wait on it.
+    fn digest_all(&self) -> Vec<(PathBuf, Result<String>)> {
+        parallel::map_ordered(&self.files, self.jobs, |file| {
+            (file.clone(), self.digest(file, std::io::stdin()))
+        })
+    }
+
+    /// Hashes the file, or `stdin` when the file is `-`.
+    fn digest<R: Read>(&self, file: &Path, stdin: R) -> Result<String> {
+        if file == Path::new(STDIN_PATH) {
+            FileHandler::checksum_reader(stdin, &self.algorithm)
+        } else {
+            FileHandler::checksum(file, &self.algorithm)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stdin_matches_file_digest() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("artifact.txt");
+        FileHandler::write(&file_path, "release bytes").unwrap();
+
+        let cmd = HashCommand::new(Vec::new(), "sha256".to_string());
+        let from_stdin = cmd
+            .digest(Path::new("-"), Cursor::new(b"release bytes"))
+            .unwrap();
+        let from_file = cmd.digest(&file_path, std::io::empty()).unwrap();
+
+        assert_eq!(from_stdin, from_file);
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let dir = TempDir::new().unwrap();
+        let mut files: Vec<PathBuf> = (0..40)
+            .map(|i| {
+                let path = dir.path().join(format!("file-{:02}.txt", i));
+                FileHandler::write(&path, &"x".repeat(i * 100)).unwrap();
+                path
+            })
+            .collect();
+        files.insert(7, dir.path().join("missing.txt"));
+
+        let summarize = |jobs| -> Vec<(PathBuf, Option<String>)> {
+            HashCommand::new(files.clone(), "sha256".to_string())
+                .with_jobs(jobs)
+                .digest_all()
+                .into_iter()
+                .map(|(file, digest)| (file, digest.ok()))
+                .collect()
+        };
+
+        let serial = summarize(1);
+        assert_eq!(serial.iter().filter(|(_, d)| d.is_none()).count(), 1);
+        assert_eq!(summarize(4), serial);
+    }
+
+    #[test]
+    fn test_files_do_not_wait_on_stdin() {
+        let dir = TempDir::new().unwrap();
+        let files: Vec<PathBuf> = (0..4)
+            .map(|i| {
+                let path = dir.path().join(format!("file-{}.txt", i));
+                FileHandler::write(&path, "bytes").unwrap();
+                path
+            })
+            .collect();
+
+        // Stands in for a worker that is busy reading `-` from stdin.
+        let _busy_stdin = std::io::stdin().lock();
+        let cmd = HashCommand::new(files, "sha256".to_string()).with_jobs(2);
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || tx.send(cmd.digest_all()).unwrap());
+
+        let digests = rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("regular files blocked on the stdin lock");
+        assert!(digests.iter().all(|(_, digest)| digest.is_ok()));
+    }
+}
@@ -1,22 +1,65 @@
 use anyhow::Result;
 use chrono::Local;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 pub struct HelloCommand {
     name: String,
     uppercase: bool,
     repeat: usize,
+    app_name: String,
+    soak: Option<Duration>,
+    interrupted: Arc<AtomicBool>,
 }
 
 impl HelloCommand {
+    /// Product name shown in the greeting; the Cargo package name is an internal detail.
+    pub const APP_NAME: &'static str = "ptd";
+
     pub fn new(name: String, uppercase: bool, repeat: usize) -> Self {
         Self {
             name,
             uppercase,
             repeat,
+            app_name: Self::APP_NAME.to_string(),
+            soak: None,
+            interrupted: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Instead of `repeat` greetings, greets in a loop for this long (or until
+    /// Ctrl-C) and reports how many iterations ran.
+    pub fn with_soak(mut self, soak: Option<Duration>) -> Self {
+        self.soak = soak;
+        self
+    }
+
+    /// Flag that ends a soak early once set, typically by a Ctrl-C handler.
+    pub fn with_interrupt_flag(mut self, interrupted: Arc<AtomicBool>) -> Self {
+        self.interrupted = interrupted;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_app_name(mut self, app_name: &str) -> Self {
+        self.app_name = app_name.to_string();
+        self
+    }
+
     pub fn execute(&self) -> Result<()> {
+        if let Some(duration) = self.soak {
+            let start = Instant::now();
+            let iterations = self.soak(duration, std::io::stdout().lock())?;
+            eprintln!(
+                "Soak finished: {} iterations in {:.2}s",
+                iterations,
+                start.elapsed().as_secs_f64()
+            );
+            return Ok(());
+        }
+
         let greeting = self.build_greeting();
 
         for _ in 0..self.repeat {
@@ -30,9 +73,32 @@ impl HelloCommand {
         Ok(())
     }
 
+    /// Writes the greeting to `output` until `duration` elapses or the interrupt
+    /// flag is set and returns the number of greetings written.
+    fn soak<W: Write>(&self, duration: Duration, mut output: W) -> Result<usize> {
+        let greeting = if self.uppercase {
+            self.build_greeting().to_uppercase()
+        } else {
+            self.build_greeting()
+        };
+
+        let deadline = Instant::now() + duration;
+        let mut iterations = 0;
+        while Instant::now() < deadline && !self.interrupted.load(Ordering::SeqCst) {
+            writeln!(output, "{}", greeting)?;
+            iterations += 1;
+        }
+        output.flush()?;
+
+        Ok(iterations)
+    }
+
     fn build_greeting(&self) -> String {
         let time_of_day = self.get_time_of_day();
-        format!("{}, {}! Welcome to BasicCli", time_of_day, self.name)
+        format!(
+            "{}, {}! Welcome to {}",
+            time_of_day, self.name, self.app_name
+        )
     }
 
     fn get_time_of_day(&self) -> &str {
@@ -86,6 +152,33 @@ mod tests {
         assert!(command.execute().is_ok());
     }
 
+    #[test]
+    fn test_short_soak_counts_iterations() {
+        let command = HelloCommand::new("Soak".to_string(), true, 1);
+        let mut output = Vec::new();
+        let iterations = command
+            .soak(Duration::from_millis(20), &mut output)
+            .unwrap();
+
+        assert!(iterations > 0);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), iterations);
+        assert!(output.lines().all(|line| line.contains("SOAK")));
+    }
+
+    #[test]
+    fn test_soak_stops_when_interrupted() {
+        let command = HelloCommand::new("Soak".to_string(), false, 1)
+            .with_interrupt_flag(Arc::new(AtomicBool::new(true)));
+        let mut output = Vec::new();
+
+        assert_eq!(
+            command.soak(Duration::from_secs(5), &mut output).unwrap(),
+            0
+        );
+        assert!(output.is_empty());
+    }
+
     #[test]
     fn test_time_of_day() {
         let command = HelloCommand::new("Test".to_string(), false, 1);
@@ -98,6 +191,12 @@ mod tests {
         let command = HelloCommand::new("Test".to_string(), false, 1);
         let greeting = command.build_greeting();
         assert!(greeting.contains("Test"));
-        assert!(greeting.contains("Welcome to BasicCli"));
+        assert!(greeting.ends_with("Welcome to ptd"));
+    }
+
+    #[test]
+    fn test_app_name_override() {
+        let command = HelloCommand::new("Test".to_string(), false, 1).with_app_name("BasicCli");
+        assert!(command.build_greeting().ends_with("Welcome to BasicCli"));
     }
 }
@@ -0,0 +1,78 @@
+use anyhow::{bail, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use crate::utils::file_handler::FileError;
+use crate::utils::format;
+
+pub struct HexdumpCommand {
+    file: PathBuf,
+    offset: u64,
+    length: Option<u64>,
+}
+
+impl HexdumpCommand {
+    pub fn new(file: PathBuf, offset: u64, length: Option<u64>) -> Self {
+        Self {
+            file,
+            offset,
+            length,
+        }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        print!("{}", self.dump()?);
+        Ok(())
+    }
+
+    fn dump(&self) -> Result<String> {
+        let bytes = self.read_window()?;
+        Ok(format::hexdump_at(&bytes, self.offset))
+    }
+
+    fn read_window(&self) -> Result<Vec<u8>> {
+        if !self.file.exists() {
+            bail!(FileError::NotFound(self.file.display().to_string()));
+        }
+
+        let mut file = File::open(&self.file)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        let mut buffer = Vec::new();
+        match self.length {
+            Some(length) => file.take(length).read_to_end(&mut buffer)?,
+            None => file.read_to_end(&mut buffer)?,
+        };
+
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::file_handler::FileHandler;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_windowed_dump() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("data.bin");
+        FileHandler::write(&file_path, "0123456789ABCDEFGHIJ").unwrap();
+
+        let cmd = HexdumpCommand::new(file_path, 16, Some(4));
+        let output = cmd.dump().unwrap();
+
+        assert!(output.starts_with("00000010  47 48 49 4a "));
+        assert!(output.contains("|GHIJ|"));
+        assert!(output.ends_with("00000014\n"));
+        assert!(cmd.execute().is_ok());
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let cmd = HexdumpCommand::new(PathBuf::from("/nonexistent/file.bin"), 0, None);
+        assert!(cmd.execute().is_err());
+    }
+}
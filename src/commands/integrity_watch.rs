@@ -0,0 +1,347 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::utils::file_handler::{FileHandler, WalkOptions};
+use crate::utils::logger::{LogLevel, Logger};
+use crate::utils::watcher::{PathWatcher, POLL_INTERVAL};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct IntegrityWatchCommand {
+    paths: Vec<PathBuf>,
+    baseline: PathBuf,
+    algorithm: String,
+    interval: Duration,
+    timeout: Option<Duration>,
+    interrupted: Arc<AtomicBool>,
+}
+
+/// Checksums recorded for every monitored file. Keys are `/`-joined paths
+/// relative to the parent of each canonicalized root (`etc/passwd` for a root
+/// of `./etc` or `/srv/etc`), so the baseline matches however the root is
+/// spelled and whichever directory the command runs from.
+#[derive(Debug, Serialize, Deserialize)]
+struct Baseline {
+    algorithm: String,
+    files: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IntegrityEvent {
+    Changed(String),
+    Added(String),
+    Deleted(String),
+}
+
+impl fmt::Display for IntegrityEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrityEvent::Changed(path) => write!(f, "Changed: {}", path),
+            IntegrityEvent::Added(path) => write!(f, "Added: {}", path),
+            IntegrityEvent::Deleted(path) => write!(f, "Deleted: {}", path),
+        }
+    }
+}
+
+impl IntegrityWatchCommand {
+    pub fn new(paths: Vec<PathBuf>, baseline: PathBuf) -> Self {
+        Self {
+            paths,
+            baseline,
+            algorithm: "sha256".to_string(),
+            interval: DEFAULT_INTERVAL,
+            timeout: None,
+            interrupted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Hash algorithm for a new baseline; an existing baseline keeps the one it was written with.
+    pub fn with_algorithm(mut self, algorithm: String) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Longest gap between re-hashing passes when no filesystem notification arrives.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Stops watching after this long; a zero duration means watch until interrupted.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// Flag that stops watching once set, typically by a Ctrl-C handler.
+    pub fn with_interrupt_flag(mut self, interrupted: Arc<AtomicBool>) -> Self {
+        self.interrupted = interrupted;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let mut alerts = 0;
+        self.run(&Logger::new(LogLevel::Info), |_| {
+            alerts += 1;
+            !self.interrupted.load(Ordering::SeqCst)
+        })?;
+
+        if alerts > 0 {
+            bail!("{} integrity violation(s) detected", alerts);
+        }
+        Ok(())
+    }
+
+    /// Re-hashes the monitored files whenever the filesystem reports activity
+    /// under them, and every interval regardless in case a notification was
+    /// missed. Logs an error for each file that changed, appeared or
+    /// disappeared since the previous pass. Each change is reported once; the
+    /// baseline on disk is never rewritten after it is first created.
+    fn run<F>(&self, logger: &Logger, mut keep_going: F) -> Result<()>
+    where
+        F: FnMut(&IntegrityEvent) -> bool,
+    {
+        let baseline = self.load_or_create_baseline(logger)?;
+        let watcher = PathWatcher::new(&self.paths)?;
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        logger.info(&format!(
+            "Monitoring {} file(s) against {}",
+            baseline.files.len(),
+            self.baseline.display()
+        ));
+
+        let mut known = baseline.files;
+        let mut next_scan = Instant::now();
+        loop {
+            if self.interrupted.load(Ordering::SeqCst)
+                || deadline.is_some_and(|d| Instant::now() >= d)
+            {
+                return Ok(());
+            }
+
+            let notified = !watcher.changed_paths(POLL_INTERVAL)?.is_empty();
+            if !notified && Instant::now() < next_scan {
+                continue;
+            }
+
+            let current = self.snapshot(&baseline.algorithm, &known, logger);
+            for event in diff(&known, &current) {
+                logger.error(&event.to_string());
+                if !keep_going(&event) {
+                    return Ok(());
+                }
+            }
+            known = current;
+            next_scan = Instant::now() + self.interval;
+        }
+    }
+
+    fn load_or_create_baseline(&self, logger: &Logger) -> Result<Baseline> {
+        if self.baseline.exists() {
+            return FileHandler::read_json(&self.baseline);
+        }
+
+        let baseline = Baseline {
+            algorithm: self.algorithm.clone(),
+            files: self.snapshot(&self.algorithm, &BTreeMap::new(), logger),
+        };
+        let json = serde_json::to_string_pretty(&baseline)?;
+        FileHandler::atomic_write(&self.baseline, &json)?;
+        logger.info(&format!("Wrote baseline {}", self.baseline.display()));
+        Ok(baseline)
+    }
+
+    /// Hashes every file under the monitored paths. Files that vanish before
+    /// they can be hashed are left out so they surface as deletions. Any other
+    /// failure is logged and the `previous` digest is kept, so a single
+    /// unreadable file neither stops the monitor nor hides later changes. The
+    /// baseline file itself is skipped when it lives inside a monitored directory.
+    fn snapshot(
+        &self,
+        algorithm: &str,
+        previous: &BTreeMap<String, String>,
+        logger: &Logger,
+    ) -> BTreeMap<String, String> {
+        let baseline = self.baseline.canonicalize().ok();
+
+        let mut files = BTreeMap::new();
+        for root in &self.paths {
+            // A root that no longer exists contributes nothing, so all its files read as deleted.
+            let Ok(root) = root.canonicalize() else {
+                continue;
+            };
+            let base = root.parent().unwrap_or(&root);
+
+            let entries = if root.is_dir() {
+                match FileHandler::walk_dir(&root, &WalkOptions::default()) {
+                    Ok(entries) => entries,
+                    Err(_) if !root.exists() => continue,
+                    Err(e) => {
+                        let prefix = format!("{}/", FileHandler::relative_slash_path(base, &root));
+                        logger.error(&format!("Cannot scan {}: {}", root.display(), e));
+                        for (key, hash) in previous.range(prefix.clone()..) {
+                            if !key.starts_with(&prefix) {
+                                break;
+                            }
+                            files.insert(key.clone(), hash.clone());
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                vec![root.clone()]
+            };
+
+            for path in entries {
+                if !path.is_file() || baseline.as_ref() == Some(&path) {
+                    continue;
+                }
+
+                let key = FileHandler::relative_slash_path(base, &path);
+                match FileHandler::checksum(&path, algorithm) {
+                    Ok(hash) => {
+                        files.insert(key, hash);
+                    }
+                    Err(_) if !path.exists() => {}
+                    Err(e) => {
+                        logger.error(&format!("Cannot hash {}: {}", key, e));
+                        if let Some(hash) = previous.get(&key) {
+                            files.insert(key, hash.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        files
+    }
+}
+
+fn diff(
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<IntegrityEvent> {
+    let mut events = Vec::new();
+    for (path, hash) in current {
+        match previous.get(path) {
+            Some(old) if old != hash => events.push(IntegrityEvent::Changed(path.clone())),
+            Some(_) => {}
+            None => events.push(IntegrityEvent::Added(path.clone())),
+        }
+    }
+    for path in previous.keys() {
+        if !current.contains_key(path) {
+            events.push(IntegrityEvent::Deleted(path.clone()));
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_altered_file_reports_change() {
+        let dir = TempDir::new().unwrap();
+        let watched = dir.path().join("etc");
+        let file = watched.join("passwd");
+        FileHandler::write(&file, "root:x:0:0\n").unwrap();
+        let baseline = dir.path().join("baseline.json");
+
+        let cmd = IntegrityWatchCommand::new(vec![watched], baseline.clone())
+            .with_interval(Duration::from_millis(50))
+            .with_timeout(Duration::from_secs(5));
+
+        let tamper_target = file.clone();
+        let tamper = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            FileHandler::write(&tamper_target, "root:x:0:0\nevil:x:0:0\n").unwrap();
+        });
+
+        let mut events = Vec::new();
+        cmd.run(&Logger::default(), |event| {
+            events.push(event.clone());
+            false
+        })
+        .unwrap();
+        tamper.join().unwrap();
+
+        assert_eq!(
+            events,
+            vec![IntegrityEvent::Changed("etc/passwd".to_string())]
+        );
+        let written: Baseline = FileHandler::read_json(&baseline).unwrap();
+        assert_eq!(written.algorithm, "sha256");
+        assert_eq!(written.files.len(), 1);
+    }
+
+    #[test]
+    fn test_keys_do_not_depend_on_root_spelling() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("etc/passwd"), "root:x:0:0\n").unwrap();
+        FileHandler::write(dir.path().join("etc/ssh/sshd_config"), "Port 22\n").unwrap();
+        let baseline = dir.path().join("baseline.json");
+        let snapshot = |root: PathBuf| {
+            IntegrityWatchCommand::new(vec![root], baseline.clone()).snapshot(
+                "sha256",
+                &BTreeMap::new(),
+                &Logger::default(),
+            )
+        };
+
+        let direct = snapshot(dir.path().join("etc"));
+        let roundabout = snapshot(dir.path().join("etc/ssh/../../etc"));
+
+        assert_eq!(
+            direct.keys().collect::<Vec<_>>(),
+            vec!["etc/passwd", "etc/ssh/sshd_config"]
+        );
+        assert_eq!(direct, roundabout);
+    }
+
+    #[test]
+    fn test_missing_root_reads_as_deleted() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("passwd");
+        FileHandler::write(&file, "root:x:0:0\n").unwrap();
+        let cmd = IntegrityWatchCommand::new(vec![file.clone()], dir.path().join("baseline.json"));
+
+        let before = cmd.snapshot("sha256", &BTreeMap::new(), &Logger::default());
+        std::fs::remove_file(&file).unwrap();
+        let after = cmd.snapshot("sha256", &before, &Logger::default());
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![IntegrityEvent::Deleted("passwd".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_added_and_deleted() {
+        let previous = BTreeMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+        ]);
+        let current = BTreeMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("c".to_string(), "3".to_string()),
+        ]);
+
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                IntegrityEvent::Added("c".to_string()),
+                IntegrityEvent::Deleted("b".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::utils::file_handler::{FileHandler, SortBy, WalkOptions};
+use crate::utils::table::Table;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Plain,
+    Json,
+    Csv,
+}
+
+pub struct LsCommand {
+    dir: PathBuf,
+    format: ListFormat,
+    output: Option<PathBuf>,
+    recursive_size: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ListEntry {
+    name: String,
+    size: u64,
+    mtime: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+impl LsCommand {
+    pub fn new(dir: PathBuf, format: ListFormat) -> Self {
+        Self {
+            dir,
+            format,
+            output: None,
+            recursive_size: false,
+        }
+    }
+
+    /// Writes JSON or CSV listings to this file instead of stdout.
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Reports a directory's size as the total of all files beneath it.
+    pub fn with_recursive_size(mut self, recursive_size: bool) -> Self {
+        self.recursive_size = recursive_size;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let entries = self.entries()?;
+
+        match (self.format, &self.output) {
+            (ListFormat::Plain, _) => print!("{}", format_plain(&entries)),
+            (ListFormat::Json, Some(path)) => FileHandler::write_json(path, &entries, true)?,
+            (ListFormat::Json, None) => println!("{}", serde_json::to_string_pretty(&entries)?),
+            (ListFormat::Csv, Some(path)) => write_csv(&entries, FileHandler::open_writer(path)?)?,
+            (ListFormat::Csv, None) => write_csv(&entries, io::stdout().lock())?,
+        }
+
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<ListEntry>> {
+        let options = WalkOptions::default();
+        FileHandler::list_dir(&self.dir, &options, SortBy::Name, false)?
+            .into_iter()
+            .map(|path| {
+                let stats = FileHandler::stats(&path)?;
+                let size = if stats.is_directory && self.recursive_size {
+                    FileHandler::dir_size(&path, &options)?
+                } else {
+                    stats.size
+                };
+                let kind = if stats.is_directory {
+                    "directory"
+                } else if stats.is_file {
+                    "file"
+                } else {
+                    "other"
+                };
+
+                Ok(ListEntry {
+                    name: path
+                        .file_name()
+                        .map_or_else(String::new, |n| n.to_string_lossy().into_owned()),
+                    size,
+                    mtime: DateTime::<Utc>::from(stats.modified_at).to_rfc3339(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+/// One row per entry, with columns in `ListEntry` field order.
+fn write_csv<W: Write>(entries: &[ListEntry], output: W) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(output);
+    for entry in entries {
+        writer.serialize(entry)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn format_plain(entries: &[ListEntry]) -> String {
+    let mut table = Table::new().with_header(["Name", "Size", "Modified", "Type"]);
+    for entry in entries {
+        table.add_row([
+            entry.name.clone(),
+            entry.size.to_string(),
+            entry.mtime.clone(),
+            entry.kind.to_string(),
+        ]);
+    }
+    table.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_json_has_one_entry_per_file() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.txt"), "abc").unwrap();
+        FileHandler::write(dir.path().join("b.json"), "{}").unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let output = out_dir.path().join("listing.json");
+
+        LsCommand::new(dir.path().to_path_buf(), ListFormat::Json)
+            .with_output(Some(output.clone()))
+            .execute()
+            .unwrap();
+
+        let listing: Value = FileHandler::read_json(&output).unwrap();
+        let listing = listing.as_array().unwrap();
+        assert_eq!(listing.len(), 2);
+        assert_eq!(listing[0]["name"], "a.txt");
+        assert_eq!(listing[0]["size"], 3);
+        assert_eq!(listing[0]["type"], "file");
+        assert!(listing[0]["mtime"]
+            .as_str()
+            .unwrap()
+            .parse::<DateTime<Utc>>()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_csv_columns_in_fixed_order() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.txt"), "abc").unwrap();
+
+        let entries = LsCommand::new(dir.path().to_path_buf(), ListFormat::Csv)
+            .entries()
+            .unwrap();
+        let mut output = Vec::new();
+        write_csv(&entries, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("name,size,mtime,type"));
+        assert_eq!(
+            lines.next(),
+            Some(format!("a.txt,3,{},file", entries[0].mtime).as_str())
+        );
+    }
+
+    #[test]
+    fn test_recursive_size_for_directories() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("nested/deep/x.bin"), "12345").unwrap();
+        FileHandler::write(dir.path().join("nested/y.bin"), "678").unwrap();
+
+        let recursive =
+            LsCommand::new(dir.path().to_path_buf(), ListFormat::Json).with_recursive_size(true);
+        let entries = recursive.entries().unwrap();
+        assert_eq!(entries[0].kind, "directory");
+        assert_eq!(entries[0].size, 8);
+    }
+}
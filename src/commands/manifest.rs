@@ -0,0 +1,73 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::utils::file_handler::{FileHandler, WalkOptions};
+
+pub struct ManifestCommand {
+    dir: PathBuf,
+    algorithm: String,
+}
+
+impl ManifestCommand {
+    pub fn new(dir: PathBuf, algorithm: String) -> Self {
+        Self { dir, algorithm }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        for line in self.lines()? {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+
+    /// One `<hash>  <path>` line per file under the directory, sorted by path.
+    /// Paths are relative and `/`-separated so `verify` can check the list when
+    /// it is saved inside the directory.
+    fn lines(&self) -> Result<Vec<String>> {
+        let mut files: Vec<(String, PathBuf)> =
+            FileHandler::walk_dir(&self.dir, &WalkOptions::default())?
+                .into_iter()
+                .filter(|path| path.is_file())
+                .map(|path| (FileHandler::relative_slash_path(&self.dir, &path), path))
+                .collect();
+        files.sort();
+
+        files
+            .iter()
+            .map(|(name, path)| {
+                let digest = FileHandler::checksum(path, &self.algorithm)?;
+                Ok(format!("{}  {}", digest, name))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_one_line_per_file_sorted() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("b.txt"), "b").unwrap();
+        FileHandler::write(dir.path().join("a/z.bin"), "z").unwrap();
+        FileHandler::write(dir.path().join("a/c.txt"), "c").unwrap();
+
+        let lines = ManifestCommand::new(dir.path().to_path_buf(), "sha256".to_string())
+            .lines()
+            .unwrap();
+
+        let names: Vec<&str> = lines.iter().map(|l| &l[66..]).collect();
+        assert_eq!(names, vec!["a/c.txt", "a/z.bin", "b.txt"]);
+        for line in &lines {
+            let (hash, _) = line.split_once("  ").unwrap();
+            assert_eq!(hash.len(), 64);
+            assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        }
+        assert_eq!(
+            &lines[2][..64],
+            FileHandler::checksum_bytes(b"b", "sha256").unwrap()
+        );
+    }
+}
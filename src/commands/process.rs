@@ -0,0 +1,596 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::utils::assertion::Assertion;
+use crate::utils::file_handler::{FileError, FileHandler};
+use crate::utils::json;
+use crate::utils::logger::{LogLevel, Logger};
+
+pub struct ProcessCommand {
+    files: Vec<PathBuf>,
+    options: ProcessOptions,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOptions {
+    pub glob: Option<String>,
+    pub pretty: bool,
+    pub stats: bool,
+    pub stats_json: bool,
+    pub fail_fast: bool,
+    /// `/pointer=value` assignments applied before printing; values parse as JSON, else strings.
+    pub set: Vec<String>,
+    /// JSON pointers removed after the assignments; missing paths are ignored.
+    pub unset: Vec<String>,
+    /// Keep only these top-level keys.
+    pub only: Vec<String>,
+    /// Drop these top-level keys.
+    pub except: Vec<String>,
+    /// JSON pointer selecting the node to print instead of the whole document.
+    pub query: Option<String>,
+    /// Treat each file as a top-level array and process it element by element.
+    pub stream_array: bool,
+    /// Pretty-print with this indent unit instead of the default two spaces.
+    pub indent: Option<String>,
+    /// Assertions such as `$.status == "ok"` that every document must satisfy.
+    pub asserts: Vec<String>,
+    /// Reject documents whose objects repeat a key.
+    pub strict: bool,
+    /// Print RFC 8785 canonical JSON instead of the usual layout.
+    pub canonical: bool,
+    /// Keep only the first `n` elements of a top-level array.
+    pub head: Option<usize>,
+    /// Keep only the last `n` elements of a top-level array.
+    pub tail: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct DocumentStats {
+    file: String,
+    size_bytes: u64,
+    key_count: usize,
+    depth: usize,
+    nodes: NodeCounts,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elements: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct NodeCounts {
+    object: usize,
+    array: usize,
+    string: usize,
+    number: usize,
+    boolean: usize,
+    null: usize,
+}
+
+#[derive(Debug)]
+struct ProcessOutcome {
+    path: PathBuf,
+    error: Option<anyhow::Error>,
+}
+
+impl ProcessCommand {
+    pub fn new(files: Vec<PathBuf>, options: ProcessOptions) -> Self {
+        Self { files, options }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let logger = Logger::new(if self.options.stats {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        });
+
+        let mut outcomes = self.run(&logger)?;
+        if outcomes.len() == 1 {
+            return match outcomes.remove(0).error {
+                Some(e) => Err(e),
+                None => Ok(()),
+            };
+        }
+
+        let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+        for outcome in &outcomes {
+            let status = if outcome.error.is_some() {
+                "FAILED"
+            } else {
+                "OK"
+            };
+            logger.info(&format!("{:7} {}", status, outcome.path.display()));
+        }
+        logger.info(&format!(
+            "Processed {} files: {} passed, {} failed",
+            outcomes.len(),
+            outcomes.len() - failed,
+            failed
+        ));
+
+        if failed > 0 {
+            bail!("{} of {} files failed to process", failed, outcomes.len());
+        }
+
+        Ok(())
+    }
+
+    fn run(&self, logger: &Logger) -> Result<Vec<ProcessOutcome>> {
+        let mut paths = self.files.clone();
+        if let Some(pattern) = &self.options.glob {
+            paths.extend(FileHandler::glob(pattern)?);
+        }
+
+        if paths.is_empty() {
+            bail!("No files to process");
+        }
+
+        let multiple = paths.len() > 1;
+        let mut outcomes = Vec::new();
+        for path in paths {
+            let error = self.process_file(&path, logger).err();
+            if let Some(e) = error.as_ref().filter(|_| multiple) {
+                logger.error(&e.to_string());
+            }
+            let stop = error.is_some() && self.options.fail_fast;
+
+            outcomes.push(ProcessOutcome { path, error });
+            if stop {
+                break;
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    fn process_file(&self, file: &Path, logger: &Logger) -> Result<()> {
+        logger.info(&format!("Processing file: {}", file.display()));
+
+        if !file.exists() {
+            bail!(FileError::NotFound(file.display().to_string()));
+        }
+
+        if self.options.stream_array {
+            let stats = self.stream_file(file)?;
+            logger.info(&format!(
+                "Streamed {} array elements",
+                stats.elements.unwrap_or(0)
+            ));
+            if self.options.stats_json {
+                eprintln!("{}", serde_json::to_string(&stats)?);
+            }
+            if self.options.stats {
+                logger.info(&format!("File size: {} bytes", stats.size_bytes));
+                logger.info("Processing complete");
+            }
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(file)?;
+        let parsed = if self.options.strict {
+            json::from_str_strict(&content)
+        } else {
+            serde_json::from_str(&content)
+        };
+        let mut data =
+            parsed.map_err(|e| FileError::InvalidJson(format!("{}: {}", file.display(), e)))?;
+        self.transform(&mut data)?;
+        self.slice(&mut data)?;
+
+        if let Some(obj) = data.as_object() {
+            logger.info(&format!("Successfully parsed JSON with {} keys", obj.len()));
+        }
+
+        self.print(&data)?;
+
+        let failures = self.failed_assertions(&data)?;
+        for expression in &failures {
+            logger.error(&format!("Assertion failed: {}", expression));
+        }
+        if !failures.is_empty() {
+            bail!(
+                "{} of {} assertions failed for {}",
+                failures.len(),
+                self.options.asserts.len(),
+                file.display()
+            );
+        }
+
+        if self.options.stats_json {
+            // Stats go to stderr so stdout stays a clean document stream.
+            eprintln!(
+                "{}",
+                serde_json::to_string(&Self::document_stats(file, &data)?)?
+            );
+        }
+
+        if self.options.stats {
+            let metadata = std::fs::metadata(file)?;
+            logger.info(&format!("File size: {} bytes", metadata.len()));
+            logger.info("Processing complete");
+        }
+
+        Ok(())
+    }
+
+    /// Processes a top-level array without loading it whole: each element is
+    /// transformed, queried and printed on its own, and stats are accumulated.
+    fn stream_file(&self, file: &Path) -> Result<DocumentStats> {
+        let mut stats = DocumentStats {
+            file: file.display().to_string(),
+            size_bytes: std::fs::metadata(file)?.len(),
+            ..Default::default()
+        };
+
+        let count = json::for_each_array_element(std::fs::File::open(file)?, |mut element| {
+            self.transform(&mut element)?;
+            stats.depth = stats.depth.max(count_nodes(&element, &mut stats.nodes));
+            self.print(&element)
+        })
+        .map_err(|e| match e.downcast::<serde_json::Error>() {
+            Ok(e) => FileError::InvalidJson(format!("{}: {}", file.display(), e)).into(),
+            Err(e) => e,
+        })?;
+        // The array itself sits one level above its elements.
+        stats.nodes.array += 1;
+        stats.depth += 1;
+        stats.elements = Some(count);
+
+        Ok(stats)
+    }
+
+    /// Prints `data`, or the node selected by `--query` (`null` when it is absent).
+    fn print(&self, data: &Value) -> Result<()> {
+        let selected = match &self.options.query {
+            Some(pointer) => json::get(data, pointer).unwrap_or(&Value::Null),
+            None => data,
+        };
+
+        if self.options.canonical {
+            println!("{}", json::to_canonical_string(selected)?);
+        } else if let Some(indent) = &self.options.indent {
+            println!("{}", json::to_string_indented(selected, indent)?);
+        } else if self.options.pretty {
+            println!("{}", serde_json::to_string_pretty(selected)?);
+        } else {
+            println!("{}", serde_json::to_string(selected)?);
+        }
+        Ok(())
+    }
+
+    /// Returns the expressions of every assertion `data` does not satisfy.
+    fn failed_assertions(&self, data: &Value) -> Result<Vec<String>> {
+        let mut failures = Vec::new();
+        for expression in &self.options.asserts {
+            let assertion = Assertion::parse(expression)?;
+            if !assertion.evaluate(data) {
+                failures.push(assertion.expression().to_string());
+            }
+        }
+        Ok(failures)
+    }
+
+    fn transform(&self, data: &mut Value) -> Result<()> {
+        for assignment in &self.options.set {
+            let (pointer, value) = parse_assignment(assignment)?;
+            json::set(data, pointer, value)?;
+        }
+        for pointer in &self.options.unset {
+            json::remove(data, pointer);
+        }
+        self.project(data)
+    }
+
+    /// Applies `--head`/`--tail` to a top-level array.
+    fn slice(&self, data: &mut Value) -> Result<()> {
+        if self.options.head.is_none() && self.options.tail.is_none() {
+            return Ok(());
+        }
+        let Some(elements) = data.as_array_mut() else {
+            bail!("--head/--tail require a JSON array at the top level");
+        };
+
+        if let Some(n) = self.options.head {
+            elements.truncate(n);
+        }
+        if let Some(n) = self.options.tail {
+            let skip = elements.len().saturating_sub(n);
+            elements.drain(..skip);
+        }
+        Ok(())
+    }
+
+    fn project(&self, data: &mut Value) -> Result<()> {
+        let (only, except) = (&self.options.only, &self.options.except);
+        if only.is_empty() && except.is_empty() {
+            return Ok(());
+        }
+        if !only.is_empty() && !except.is_empty() {
+            bail!("--only and --except cannot be used together");
+        }
+
+        let Some(map) = data.as_object_mut() else {
+            bail!("--only/--except require a JSON object at the top level");
+        };
+        if only.is_empty() {
+            map.retain(|key, _| !except.contains(key));
+        } else {
+            map.retain(|key, _| only.contains(key));
+        }
+
+        Ok(())
+    }
+
+    fn document_stats(file: &Path, data: &Value) -> Result<DocumentStats> {
+        let mut stats = DocumentStats {
+            file: file.display().to_string(),
+            size_bytes: std::fs::metadata(file)?.len(),
+            key_count: data.as_object().map_or(0, |obj| obj.len()),
+            ..Default::default()
+        };
+        stats.depth = count_nodes(data, &mut stats.nodes);
+        Ok(stats)
+    }
+}
+
+/// Splits `/a/b=1` into its pointer and value, treating non-JSON values as strings.
+fn parse_assignment(assignment: &str) -> Result<(&str, Value)> {
+    let Some((pointer, raw)) = assignment.split_once('=') else {
+        bail!(
+            "Invalid assignment '{}': expected /pointer=value",
+            assignment
+        );
+    };
+    let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+    Ok((pointer, value))
+}
+
+/// Tallies every node by type and returns the nesting depth of `value`.
+fn count_nodes(value: &Value, counts: &mut NodeCounts) -> usize {
+    match value {
+        Value::Object(map) => {
+            counts.object += 1;
+            1 + map
+                .values()
+                .map(|v| count_nodes(v, counts))
+                .max()
+                .unwrap_or(0)
+        }
+        Value::Array(items) => {
+            counts.array += 1;
+            1 + items
+                .iter()
+                .map(|v| count_nodes(v, counts))
+                .max()
+                .unwrap_or(0)
+        }
+        Value::String(_) => {
+            counts.string += 1;
+            0
+        }
+        Value::Number(_) => {
+            counts.number += 1;
+            0
+        }
+        Value::Bool(_) => {
+            counts.boolean += 1;
+            0
+        }
+        Value::Null => {
+            counts.null += 1;
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_single_file() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("data.json");
+        FileHandler::write(&file_path, r#"{"name": "test"}"#).unwrap();
+
+        let options = ProcessOptions {
+            pretty: true,
+            stats: true,
+            ..Default::default()
+        };
+        let cmd = ProcessCommand::new(vec![file_path], options);
+        assert!(cmd.execute().is_ok());
+    }
+
+    #[test]
+    fn test_strict_rejects_repeated_key() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("config.json");
+        FileHandler::write(&file_path, r#"{"port": 80, "host": "a", "port": 8080}"#).unwrap();
+
+        let lenient = ProcessCommand::new(vec![file_path.clone()], ProcessOptions::default());
+        assert!(lenient.execute().is_ok());
+
+        let options = ProcessOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let error = ProcessCommand::new(vec![file_path], options)
+            .execute()
+            .unwrap_err();
+        assert!(error.to_string().contains("duplicate key `port`"));
+    }
+
+    #[test]
+    fn test_head_and_tail_slice_arrays() {
+        let head = ProcessCommand::new(
+            vec![],
+            ProcessOptions {
+                head: Some(2),
+                ..Default::default()
+            },
+        );
+        let mut data = serde_json::json!([1, 2, 3, 4, 5]);
+        head.slice(&mut data).unwrap();
+        assert_eq!(data, serde_json::json!([1, 2]));
+
+        let tail = ProcessCommand::new(
+            vec![],
+            ProcessOptions {
+                tail: Some(10),
+                ..Default::default()
+            },
+        );
+        let mut data = serde_json::json!([1, 2, 3]);
+        tail.slice(&mut data).unwrap();
+        assert_eq!(data, serde_json::json!([1, 2, 3]));
+
+        let error = head.slice(&mut serde_json::json!({"a": 1})).unwrap_err();
+        assert!(error.to_string().contains("JSON array"));
+    }
+
+    #[test]
+    fn test_set_assignments() {
+        let options = ProcessOptions {
+            set: vec!["/a/b=1".to_string(), "/name=demo".to_string()],
+            unset: vec!["/secret".to_string(), "/not/there".to_string()],
+            ..Default::default()
+        };
+        let cmd = ProcessCommand::new(vec![], options);
+
+        let mut data = serde_json::json!({"name": "old", "secret": "x"});
+        cmd.transform(&mut data).unwrap();
+        assert_eq!(data, serde_json::json!({"a": {"b": 1}, "name": "demo"}));
+
+        assert!(parse_assignment("/missing-equals").is_err());
+    }
+
+    #[test]
+    fn test_only_and_except_projection() {
+        let except = ProcessCommand::new(
+            vec![],
+            ProcessOptions {
+                except: vec!["password".to_string()],
+                ..Default::default()
+            },
+        );
+        let mut data = serde_json::json!({"user": "a", "password": "hunter2"});
+        except.transform(&mut data).unwrap();
+        assert_eq!(data, serde_json::json!({"user": "a"}));
+
+        let only = ProcessCommand::new(
+            vec![],
+            ProcessOptions {
+                only: vec!["a".to_string(), "b".to_string()],
+                ..Default::default()
+            },
+        );
+        let mut data = serde_json::json!({"a": 1, "b": 2, "c": 3});
+        only.transform(&mut data).unwrap();
+        assert_eq!(data, serde_json::json!({"a": 1, "b": 2}));
+
+        assert!(only.transform(&mut serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_stream_array_counts_elements() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("large.json");
+        let mut content = String::from("[");
+        for i in 0..20_000 {
+            if i > 0 {
+                content.push(',');
+            }
+            content.push_str(&format!(r#"{{"id": {}, "tags": ["a", "b"]}}"#, i));
+        }
+        content.push(']');
+        FileHandler::write(&file_path, &content).unwrap();
+
+        let options = ProcessOptions {
+            stream_array: true,
+            query: Some("/id".to_string()),
+            ..Default::default()
+        };
+        let cmd = ProcessCommand::new(vec![file_path.clone()], options);
+        let stats = cmd.stream_file(&file_path).unwrap();
+        assert_eq!(stats.elements, Some(20_000));
+        assert_eq!(stats.nodes.object, 20_000);
+        assert_eq!(stats.depth, 3);
+
+        FileHandler::write(&file_path, r#"{"not": "an array"}"#).unwrap();
+        let err = cmd.stream_file(&file_path).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FileError>(),
+            Some(FileError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_assertions_report_failures() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("resp.json");
+        FileHandler::write(&file_path, r#"{"status": "ok", "items": []}"#).unwrap();
+
+        let options = ProcessOptions {
+            asserts: vec![
+                r#"$.status == "ok""#.to_string(),
+                "$.items length > 0".to_string(),
+            ],
+            ..Default::default()
+        };
+        let cmd = ProcessCommand::new(vec![file_path.clone()], options);
+
+        let data: Value = FileHandler::read_json(&file_path).unwrap();
+        assert_eq!(
+            cmd.failed_assertions(&data).unwrap(),
+            vec!["$.items length > 0".to_string()]
+        );
+        let err = cmd.execute().unwrap_err();
+        assert!(err.to_string().starts_with("1 of 2 assertions failed"));
+    }
+
+    #[test]
+    fn test_multiple_files_continue_past_failure() {
+        let dir = TempDir::new().unwrap();
+        let bad = dir.path().join("bad.json");
+        let good = dir.path().join("good.json");
+        FileHandler::write(&bad, "{ not json").unwrap();
+        FileHandler::write(&good, r#"{"ok": true}"#).unwrap();
+
+        let cmd = ProcessCommand::new(vec![bad.clone(), good.clone()], ProcessOptions::default());
+        let outcomes = cmd.run(&Logger::default()).unwrap();
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes[0].error.is_some());
+        assert!(outcomes[1].error.is_none());
+        assert!(cmd.execute().is_err());
+
+        let options = ProcessOptions {
+            fail_fast: true,
+            ..Default::default()
+        };
+        let fail_fast = ProcessCommand::new(vec![bad, good], options);
+        assert_eq!(fail_fast.run(&Logger::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_glob_pattern() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.json"), "[]").unwrap();
+        FileHandler::write(dir.path().join("b.json"), "{}").unwrap();
+        FileHandler::write(dir.path().join("skip.txt"), "text").unwrap();
+
+        let pattern = dir.path().join("*.json").display().to_string();
+        let options = ProcessOptions {
+            glob: Some(pattern),
+            ..Default::default()
+        };
+        let cmd = ProcessCommand::new(vec![], options);
+        let outcomes = cmd.run(&Logger::default()).unwrap();
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| o.error.is_none()));
+    }
+}
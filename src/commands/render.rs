@@ -0,0 +1,68 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::utils::file_handler::FileHandler;
+use crate::utils::template;
+
+pub struct RenderCommand {
+    template: PathBuf,
+    data: PathBuf,
+    allow_missing: bool,
+}
+
+impl RenderCommand {
+    pub fn new(template: PathBuf, data: PathBuf, allow_missing: bool) -> Self {
+        Self {
+            template,
+            data,
+            allow_missing,
+        }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        print!("{}", self.render()?);
+        Ok(())
+    }
+
+    fn render(&self) -> Result<String> {
+        let template = FileHandler::read(&self.template)?;
+        let data: serde_json::Value = FileHandler::read_json(&self.data)?;
+        template::render(&template, &data, self.allow_missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_from_files() {
+        let dir = TempDir::new().unwrap();
+        let template = dir.path().join("tpl.txt");
+        let data = dir.path().join("data.json");
+        FileHandler::write(&template, "Hello {{name}}").unwrap();
+        FileHandler::write(&data, r#"{"name":"X"}"#).unwrap();
+
+        let cmd = RenderCommand::new(template, data, false);
+        assert_eq!(cmd.render().unwrap(), "Hello X");
+        assert!(cmd.execute().is_ok());
+    }
+
+    #[test]
+    fn test_missing_key_errors() {
+        let dir = TempDir::new().unwrap();
+        let template = dir.path().join("tpl.txt");
+        let data = dir.path().join("data.json");
+        FileHandler::write(&template, "Hello {{name}}").unwrap();
+        FileHandler::write(&data, "{}").unwrap();
+
+        assert!(RenderCommand::new(template.clone(), data.clone(), false)
+            .render()
+            .is_err());
+        assert_eq!(
+            RenderCommand::new(template, data, true).render().unwrap(),
+            "Hello "
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{bail, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::utils::file_handler::FileHandler;
+
+pub struct VerifyCommand {
+    checksum_file: PathBuf,
+    algorithm: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VerifyStatus {
+    Ok,
+    Failed,
+    Missing,
+}
+
+impl fmt::Display for VerifyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyStatus::Ok => write!(f, "OK"),
+            VerifyStatus::Failed => write!(f, "FAILED"),
+            VerifyStatus::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChecksumEntry {
+    hash: String,
+    filename: String,
+}
+
+impl VerifyCommand {
+    pub fn new(checksum_file: PathBuf, algorithm: Option<String>) -> Self {
+        Self {
+            checksum_file,
+            algorithm,
+        }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let results = self.verify()?;
+
+        for (filename, status) in &results {
+            println!("{}: {}", filename, status);
+        }
+
+        let failures = results
+            .iter()
+            .filter(|(_, status)| *status != VerifyStatus::Ok)
+            .count();
+        if failures > 0 {
+            bail!(
+                "{} of {} listed files did not verify",
+                failures,
+                results.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks every entry, resolving filenames relative to the checksum file's directory.
+    fn verify(&self) -> Result<Vec<(String, VerifyStatus)>> {
+        let content = FileHandler::read(&self.checksum_file)?;
+        let base_dir = self.checksum_file.parent().unwrap_or(Path::new("."));
+
+        let mut results = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some(entry) = parse_line(line) else {
+                bail!(
+                    "{}:{}: improperly formatted checksum line",
+                    self.checksum_file.display(),
+                    number + 1
+                );
+            };
+
+            let path = base_dir.join(&entry.filename);
+            let status = if !path.is_file() {
+                VerifyStatus::Missing
+            } else {
+                let algorithm = match &self.algorithm {
+                    Some(algorithm) => algorithm.as_str(),
+                    None => algorithm_for_length(entry.hash.len())?,
+                };
+                if FileHandler::verify_checksum(&path, algorithm, &entry.hash)? {
+                    VerifyStatus::Ok
+                } else {
+                    VerifyStatus::Failed
+                }
+            };
+
+            results.push((entry.filename, status));
+        }
+
+        Ok(results)
+    }
+}
+
+/// Parses a coreutils `<hash>  <filename>` or `<hash> *<filename>` line.
+fn parse_line(line: &str) -> Option<ChecksumEntry> {
+    let (hash, rest) = line.split_once(' ')?;
+    let filename = rest.strip_prefix([' ', '*'])?;
+
+    if hash.is_empty() || filename.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(ChecksumEntry {
+        hash: hash.to_string(),
+        filename: filename.to_string(),
+    })
+}
+
+fn algorithm_for_length(length: usize) -> Result<&'static str> {
+    match length {
+        32 => Ok("md5"),
+        40 => Ok("sha1"),
+        64 => Ok("sha256"),
+        128 => Ok("sha512"),
+        _ => bail!(
+            "Cannot infer checksum algorithm from a {}-character hash",
+            length
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_line_formats() {
+        let text = parse_line("abc123  file.txt").unwrap();
+        assert_eq!(text.hash, "abc123");
+        assert_eq!(text.filename, "file.txt");
+
+        let binary = parse_line("abc123 *image.bin").unwrap();
+        assert_eq!(binary.filename, "image.bin");
+
+        assert!(parse_line("abc123 file.txt").is_none());
+        assert!(parse_line("not-hex  file.txt").is_none());
+    }
+
+    #[test]
+    fn test_verify_good_tampered_and_missing() {
+        let dir = TempDir::new().unwrap();
+        let good = dir.path().join("good.txt");
+        let tampered = dir.path().join("tampered.txt");
+        FileHandler::write(&good, "good content").unwrap();
+        FileHandler::write(&tampered, "original content").unwrap();
+
+        let good_hash = FileHandler::checksum(&good, "sha256").unwrap();
+        let tampered_hash = FileHandler::checksum(&tampered, "sha256").unwrap();
+        FileHandler::write(&tampered, "modified content").unwrap();
+
+        let sums = dir.path().join("SHA256SUMS");
+        FileHandler::write(
+            &sums,
+            &format!(
+                "{}  good.txt\n{} *tampered.txt\n{}  gone.txt\n",
+                good_hash, tampered_hash, good_hash
+            ),
+        )
+        .unwrap();
+
+        let cmd = VerifyCommand::new(sums, None);
+        let results = cmd.verify().unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                ("good.txt".to_string(), VerifyStatus::Ok),
+                ("tampered.txt".to_string(), VerifyStatus::Failed),
+                ("gone.txt".to_string(), VerifyStatus::Missing),
+            ]
+        );
+        assert!(cmd.execute().is_err());
+    }
+}
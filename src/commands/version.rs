@@ -3,21 +3,35 @@ use serde_json::json;
 
 pub struct VersionCommand {
     json_output: bool,
+    plain: bool,
+    compact: bool,
 }
 
 impl VersionCommand {
     pub const VERSION: &'static str = "1.0.0";
     pub const BUILD_DATE: &'static str = "2025-01-15";
 
-    pub fn new(json_output: bool) -> Self {
-        Self { json_output }
+    pub fn new(json_output: bool, plain: bool) -> Self {
+        Self {
+            json_output,
+            plain,
+            compact: false,
+        }
+    }
+
+    /// Emits `--json` output on one line for embedding in larger payloads.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
     }
 
     pub fn execute(&self) -> Result<()> {
         let version_info = self.build_version_info();
 
         if self.json_output {
-            println!("{}", serde_json::to_string_pretty(&version_info)?);
+            println!("{}", self.format_json(&version_info)?);
+        } else if self.plain {
+            print!("{}", self.format_plain(&version_info));
         } else {
             self.display_formatted(&version_info);
         }
@@ -25,7 +39,7 @@ impl VersionCommand {
         Ok(())
     }
 
-    fn build_version_info(&self) -> serde_json::Value {
+    pub fn build_version_info(&self) -> serde_json::Value {
         json!({
             "name": "BasicCli",
             "version": Self::VERSION,
@@ -37,6 +51,29 @@ impl VersionCommand {
         })
     }
 
+    fn format_json(&self, info: &serde_json::Value) -> Result<String> {
+        if self.compact {
+            Ok(serde_json::to_string(info)?)
+        } else {
+            Ok(serde_json::to_string_pretty(info)?)
+        }
+    }
+
+    /// One `Label: value` line per field, for scripts to split on `": "`.
+    fn format_plain(&self, info: &serde_json::Value) -> String {
+        [
+            ("Name", "name"),
+            ("Version", "version"),
+            ("Build Date", "build_date"),
+            ("Rust Version", "ruby_version"),
+            ("Platform", "platform"),
+            ("Description", "description"),
+        ]
+        .iter()
+        .map(|(label, key)| format!("{}: {}\n", label, info[key].as_str().unwrap_or("")))
+        .collect()
+    }
+
     fn display_formatted(&self, info: &serde_json::Value) {
         println!("╔═══════════════════════════════════════════════════════════╗");
         println!("║                    BasicCli (Rust)                        ║");
@@ -79,19 +116,43 @@ mod tests {
 
     #[test]
     fn test_default_output() {
-        let cmd = VersionCommand::new(false);
+        let cmd = VersionCommand::new(false, false);
         assert!(cmd.execute().is_ok());
     }
 
     #[test]
     fn test_json_output() {
-        let cmd = VersionCommand::new(true);
+        let cmd = VersionCommand::new(true, false);
+        assert!(cmd.execute().is_ok());
+    }
+
+    #[test]
+    fn test_compact_json_output() {
+        let cmd = VersionCommand::new(true, false).with_compact(true);
+        let info = cmd.build_version_info();
+        let output = cmd.format_json(&info).unwrap();
+
+        assert!(!output.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&output).unwrap(),
+            info
+        );
+    }
+
+    #[test]
+    fn test_plain_output() {
+        let cmd = VersionCommand::new(false, true);
+        let output = cmd.format_plain(&cmd.build_version_info());
+
+        assert!(output.contains("Version: 1.0.0\n"));
+        assert!(output.contains("Build Date: 2025-01-15\n"));
+        assert!(!output.contains(['╔', '║', '╠', '╚', '═']));
         assert!(cmd.execute().is_ok());
     }
 
     #[test]
     fn test_version_info_structure() {
-        let cmd = VersionCommand::new(false);
+        let cmd = VersionCommand::new(false, false);
         let info = cmd.build_version_info();
 
         assert!(info["name"].is_string());
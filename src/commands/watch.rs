@@ -0,0 +1,101 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::commands::convert::{ConvertCommand, DataFormat};
+use crate::utils::logger::{LogLevel, Logger};
+use crate::utils::watcher::ContentWatcher;
+
+pub struct WatchCommand {
+    file: PathBuf,
+    convert_to: Option<DataFormat>,
+    timeout: Option<Duration>,
+}
+
+impl WatchCommand {
+    pub fn new(file: PathBuf, convert_to: Option<DataFormat>) -> Self {
+        Self {
+            file,
+            convert_to,
+            timeout: None,
+        }
+    }
+
+    /// Stops watching after this long; a zero duration means watch until interrupted.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        self.run(&Logger::new(LogLevel::Info), |_| true)
+    }
+
+    /// Watches until `keep_going` returns false or the timeout elapses. With a
+    /// conversion target the sibling file is written up front and after every edit.
+    fn run<F>(&self, logger: &Logger, mut keep_going: F) -> Result<()>
+    where
+        F: FnMut(&Path) -> bool,
+    {
+        let converter = self
+            .convert_to
+            .map(|format| ConvertCommand::new(self.file.clone(), format, None));
+
+        if let Some(converter) = &converter {
+            let output = converter.convert()?;
+            logger.info(&format!("Wrote {}", output.display()));
+        }
+
+        let mut watcher = ContentWatcher::new(&self.file, "sha256")?;
+        logger.info(&format!("Watching {}", self.file.display()));
+
+        watcher.watch(self.timeout, |path| {
+            logger.info(&format!("Changed: {}", path.display()));
+
+            if let Some(converter) = &converter {
+                // A half-saved file should not end the session; report it and wait for the next edit.
+                match converter.convert() {
+                    Ok(output) => logger.info(&format!("Wrote {}", output.display())),
+                    Err(e) => logger.error(&format!("Conversion failed: {}", e)),
+                }
+            }
+
+            Ok(keep_going(path))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::file_handler::FileHandler;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_yaml_edit_updates_json() {
+        let dir = TempDir::new().unwrap();
+        let input = dir.path().join("config.yml");
+        let output = dir.path().join("config.json");
+        FileHandler::write(&input, "port: 80\n").unwrap();
+
+        let cmd = WatchCommand::new(input.clone(), Some(DataFormat::Json))
+            .with_timeout(Duration::from_secs(5));
+
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            FileHandler::atomic_write(&input, "port: 8080\n").unwrap();
+        });
+
+        let mut changes = 0;
+        cmd.run(&Logger::default(), |_| {
+            changes += 1;
+            false
+        })
+        .unwrap();
+        writer.join().unwrap();
+
+        assert_eq!(changes, 1);
+        let value: serde_json::Value = FileHandler::read_json(&output).unwrap();
+        assert_eq!(value["port"], 8080);
+    }
+}
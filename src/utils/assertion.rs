@@ -0,0 +1,250 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// A check against a JSON document, parsed from expressions such as
+/// `$.status == "ok"`, `$.items length > 0`, `$.users[0].id exists`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assertion {
+    expression: String,
+    path: Vec<Segment>,
+    length: bool,
+    check: Check,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Check {
+    Exists,
+    Compare(Operator, Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Operator {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "==" => Some(Operator::Eq),
+            "!=" => Some(Operator::Ne),
+            "<" => Some(Operator::Lt),
+            "<=" => Some(Operator::Le),
+            ">" => Some(Operator::Gt),
+            ">=" => Some(Operator::Ge),
+            _ => None,
+        }
+    }
+
+    fn holds(&self, ordering: Option<Ordering>) -> bool {
+        match (self, ordering) {
+            (Operator::Eq, Some(o)) => o == Ordering::Equal,
+            (Operator::Ne, o) => o != Some(Ordering::Equal),
+            (Operator::Lt, Some(o)) => o == Ordering::Less,
+            (Operator::Le, Some(o)) => o != Ordering::Greater,
+            (Operator::Gt, Some(o)) => o == Ordering::Greater,
+            (Operator::Ge, Some(o)) => o != Ordering::Less,
+            (_, None) => false,
+        }
+    }
+}
+
+impl Assertion {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expression = expression.trim();
+        let (path, rest) = split_path(expression);
+        let path = parse_path(path).map_err(|e| anyhow::anyhow!("{}: {}", expression, e))?;
+
+        let mut rest = rest.trim_start();
+        let length = match rest.strip_prefix("length") {
+            Some(after) if after.is_empty() || after.starts_with(char::is_whitespace) => {
+                rest = after.trim_start();
+                true
+            }
+            _ => false,
+        };
+
+        let check = if rest.is_empty() || rest == "exists" {
+            Check::Exists
+        } else {
+            let (operator, literal) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let Some(operator) = Operator::parse(operator) else {
+                bail!(
+                    "Invalid assertion '{}': unknown operator '{}'",
+                    expression,
+                    operator
+                );
+            };
+            let literal = literal.trim();
+            let Ok(value) = serde_json::from_str(literal) else {
+                bail!(
+                    "Invalid assertion '{}': '{}' is not a JSON value",
+                    expression,
+                    literal
+                );
+            };
+            Check::Compare(operator, value)
+        };
+
+        Ok(Self {
+            expression: expression.to_string(),
+            path,
+            length,
+            check,
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    pub fn evaluate(&self, document: &Value) -> bool {
+        let selected = self.select(document);
+        let selected = if self.length {
+            selected.and_then(length_of)
+        } else {
+            selected.cloned()
+        };
+
+        match (&self.check, selected) {
+            (Check::Exists, selected) => selected.is_some(),
+            (Check::Compare(operator, expected), Some(actual)) => {
+                operator.holds(compare(&actual, expected))
+            }
+            (Check::Compare(operator, _), None) => *operator == Operator::Ne,
+        }
+    }
+
+    fn select<'a>(&self, document: &'a Value) -> Option<&'a Value> {
+        self.path
+            .iter()
+            .try_fold(document, |node, segment| match segment {
+                Segment::Key(key) => node.get(key),
+                Segment::Index(index) => node.get(index),
+            })
+    }
+}
+
+/// Splits off the leading path, which ends at the first whitespace outside `['...']`.
+fn split_path(expression: &str) -> (&str, &str) {
+    let mut quoted = false;
+    for (i, c) in expression.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => return (&expression[..i], &expression[i..]),
+            _ => {}
+        }
+    }
+    (expression, "")
+}
+
+/// Parses `$`, `$.a.b`, `$.items[0]` and `$['odd key']` selections.
+fn parse_path(path: &str) -> Result<Vec<Segment>> {
+    let Some(mut rest) = path.strip_prefix('$') else {
+        bail!("path must start with '$'");
+    };
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                bail!("empty key in path");
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix("['") {
+            let Some(end) = after.find("']") else {
+                bail!("unclosed ['...'] in path");
+            };
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end + 2..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let Some(end) = after.find(']') else {
+                bail!("unclosed [...] in path");
+            };
+            let Ok(index) = after[..end].parse() else {
+                bail!("'{}' is not an array index", &after[..end]);
+            };
+            segments.push(Segment::Index(index));
+            rest = &after[end + 1..];
+        } else {
+            bail!("unexpected '{}' in path", rest);
+        }
+    }
+
+    Ok(segments)
+}
+
+fn length_of(value: &Value) -> Option<Value> {
+    match value {
+        Value::Array(items) => Some(items.len().into()),
+        Value::Object(map) => Some(map.len().into()),
+        Value::String(s) => Some(s.chars().count().into()),
+        _ => None,
+    }
+}
+
+/// Numbers compare numerically and strings lexically; other values only by equality.
+fn compare(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ if actual == expected => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(expression: &str, document: &Value) -> bool {
+        Assertion::parse(expression).unwrap().evaluate(document)
+    }
+
+    #[test]
+    fn test_comparisons_and_length() {
+        let doc = json!({"status": "ok", "count": 3, "items": [1, 2], "meta": {"odd key": null}});
+
+        assert!(check(r#"$.status == "ok""#, &doc));
+        assert!(!check(r#"$.status != "ok""#, &doc));
+        assert!(check("$.count >= 3", &doc));
+        assert!(check("$.count < 3.5", &doc));
+        assert!(check("$.items length > 0", &doc));
+        assert!(check("$.items length == 2", &doc));
+        assert!(check("$.items[1] == 2", &doc));
+        assert!(!check("$.items[5] == 2", &doc));
+        assert!(!check(r#"$.count > "a""#, &doc));
+    }
+
+    #[test]
+    fn test_existence() {
+        let doc = json!({"meta": {"odd key": null}, "items": []});
+
+        assert!(check("$.meta['odd key'] exists", &doc));
+        assert!(check("$.items", &doc));
+        assert!(!check("$.missing exists", &doc));
+        assert!(check("$.missing != 1", &doc));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(Assertion::parse("status == 1").is_err());
+        assert!(Assertion::parse("$.a ~= 1").is_err());
+        assert!(Assertion::parse("$.a == not-json").is_err());
+        assert!(Assertion::parse("$.a[x] exists").is_err());
+    }
+}
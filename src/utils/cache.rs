@@ -0,0 +1,113 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::utils::file_handler::FileHandler;
+
+/// Small persistent key-value store kept in one JSON object file. Every change
+/// is written straight back with `FileHandler::atomic_write`, so a crash never
+/// leaves a half-written cache. The in-memory copy is mutex-guarded, so one
+/// instance can be shared between threads.
+#[allow(dead_code)]
+pub struct KvCache {
+    path: PathBuf,
+    entries: Mutex<Map<String, Value>>,
+}
+
+#[allow(dead_code)]
+impl KvCache {
+    /// Loads the cache at `path`; a missing file starts empty, a malformed one is an error.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = FileHandler::read_json_or(&path)?;
+        Ok(Self {
+            path,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Returns the value for `key`, or `None` when it is absent or has another shape.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn set<T: Serialize>(&self, key: &str, value: T) -> Result<()> {
+        let value = serde_json::to_value(value)?;
+        let mut entries = self.entries.lock().unwrap();
+        if entries.get(key) == Some(&value) {
+            return Ok(());
+        }
+        entries.insert(key.to_string(), value);
+        self.persist(&entries)
+    }
+
+    /// Removes `key`, returning whether it was present.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.persist(&entries)?;
+        Ok(true)
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.is_empty() {
+            return Ok(());
+        }
+        entries.clear();
+        self.persist(&entries)
+    }
+
+    fn persist(&self, entries: &Map<String, Value>) -> Result<()> {
+        FileHandler::atomic_write(&self.path, &serde_json::to_string_pretty(entries)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_values_survive_reload() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state/cache.json");
+
+        let cache = KvCache::open(&path).unwrap();
+        assert_eq!(cache.get::<String>("last_check"), None);
+        cache.set("last_check", "2026-10-14").unwrap();
+        cache.set("runs", 3).unwrap();
+        drop(cache);
+
+        let cache = KvCache::open(&path).unwrap();
+        assert_eq!(
+            cache.get::<String>("last_check").as_deref(),
+            Some("2026-10-14")
+        );
+        assert_eq!(cache.get::<u32>("runs"), Some(3));
+
+        assert!(cache.remove("runs").unwrap());
+        assert!(!cache.remove("runs").unwrap());
+        cache.clear().unwrap();
+        assert_eq!(
+            KvCache::open(&path).unwrap().get::<String>("last_check"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_malformed_file_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cache.json");
+        FileHandler::write(&path, "{not json").unwrap();
+        assert!(KvCache::open(&path).is_err());
+    }
+}
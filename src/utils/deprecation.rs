@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::utils::logger::{LogLevel, Logger};
+
+static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Logs "`feature` is deprecated; use `replacement` instead" to stderr the first
+/// time `feature` is hit in this process and stays quiet afterwards. Returns
+/// whether a warning was logged.
+pub fn warn_once(feature: &str, replacement: &str) -> bool {
+    let logger = Logger::new(LogLevel::Warn).with_output(std::io::stderr());
+    warn_once_to(&logger, feature, replacement)
+}
+
+/// `warn_once` logging through `logger`.
+fn warn_once_to(logger: &Logger, feature: &str, replacement: &str) -> bool {
+    let first = WARNED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashSet::new)
+        .insert(feature.to_string());
+
+    if first {
+        logger.warn(&format!(
+            "{} is deprecated; use {} instead",
+            feature, replacement
+        ));
+    }
+    first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_warns_once_per_feature() {
+        let buffer = SharedBuffer::default();
+        let logger = Logger::new_with_options(LogLevel::Warn, false)
+            .with_timestamp(false)
+            .with_writer(buffer.clone());
+
+        assert!(warn_once_to(
+            &logger,
+            "--algorithm <string>",
+            "--algorithm <enum>"
+        ));
+        assert!(!warn_once_to(
+            &logger,
+            "--algorithm <string>",
+            "--algorithm <enum>"
+        ));
+        assert!(warn_once_to(
+            &logger,
+            "--format <string>",
+            "--format <enum>"
+        ));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output,
+            "WARN  | --algorithm <string> is deprecated; use --algorithm <enum> instead\n\
+             WARN  | --format <string> is deprecated; use --format <enum> instead\n"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::utils::file_handler::FileError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ErrorFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+/// Classifies an error by the first recognised cause in its chain.
+pub fn kind(err: &anyhow::Error) -> &'static str {
+    for cause in err.chain() {
+        if let Some(file_error) = cause.downcast_ref::<FileError>() {
+            return match file_error {
+                FileError::NotFound(_) => "not_found",
+                FileError::ReadError(..) | FileError::WriteError(..) => "io",
+                FileError::InvalidJson(_)
+                | FileError::InvalidYaml(_)
+                | FileError::InvalidCsv(_)
+                | FileError::InvalidIni(_) => "invalid_data",
+                FileError::UnsupportedFormat(_) => "unsupported_format",
+                FileError::OperationFailed(_) => "operation_failed",
+            };
+        }
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            return match io_error.kind() {
+                std::io::ErrorKind::NotFound => "not_found",
+                std::io::ErrorKind::PermissionDenied => "permission_denied",
+                _ => "io",
+            };
+        }
+        if cause.is::<serde_json::Error>() || cause.is::<serde_yaml::Error>() {
+            return "invalid_data";
+        }
+    }
+    "error"
+}
+
+/// Maps an error kind to a sysexits-style process exit code in JSON mode.
+/// Human output keeps exiting with 1 for every failure, as it always has.
+pub fn exit_code(err: &anyhow::Error, format: ErrorFormat) -> i32 {
+    if format == ErrorFormat::Human {
+        return 1;
+    }
+
+    match kind(err) {
+        "not_found" => 66,
+        "invalid_data" | "unsupported_format" => 65,
+        "permission_denied" => 77,
+        "io" => 74,
+        _ => 1,
+    }
+}
+
+pub fn render(err: &anyhow::Error, format: ErrorFormat) -> String {
+    match format {
+        ErrorFormat::Human => format!("Error: {:?}", err),
+        ErrorFormat::Json => json!({
+            "error": err.to_string(),
+            "kind": kind(err),
+            "context": err.chain().skip(1).map(|c| c.to_string()).collect::<Vec<_>>(),
+        })
+        .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::file_handler::FileHandler;
+    use anyhow::Context;
+
+    #[test]
+    fn test_missing_file_as_json() {
+        let err = FileHandler::read("/nonexistent/config.json")
+            .context("Loading config")
+            .unwrap_err();
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render(&err, ErrorFormat::Json)).unwrap();
+        assert_eq!(output["error"], "Loading config");
+        assert_eq!(output["kind"], "not_found");
+        assert_eq!(
+            output["context"][0],
+            "File not found: /nonexistent/config.json"
+        );
+        assert_eq!(exit_code(&err, ErrorFormat::Json), 66);
+        assert_eq!(exit_code(&err, ErrorFormat::Human), 1);
+    }
+
+    #[test]
+    fn test_human_format_and_fallback_kind() {
+        let err = anyhow::anyhow!("something broke");
+        assert!(render(&err, ErrorFormat::Human).starts_with("Error: something broke"));
+        assert_eq!(kind(&err), "error");
+        assert_eq!(exit_code(&err, ErrorFormat::Json), 1);
+    }
+}
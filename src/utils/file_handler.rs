@@ -1,14 +1,28 @@
 use anyhow::{bail, Context, Result};
-use csv::{Reader, Writer};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use bzip2::read::BzDecoder;
+use bzip2::write::BzEncoder;
+use csv::{Reader, Terminator, WriterBuilder};
+use filetime::FileTime;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha1::Sha1;
 use sha2::{Digest, Sha256, Sha512};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::{self, File};
-use std::io::Read;
-use std::path::Path;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tempfile::TempPath;
 use thiserror::Error;
 
+use crate::utils::parallel;
+
 #[derive(Error, Debug)]
 #[allow(dead_code)]
 pub enum FileError {
@@ -30,6 +44,9 @@ pub enum FileError {
     #[error("Invalid CSV: {0}")]
     InvalidCsv(String),
 
+    #[error("Invalid INI: {0}")]
+    InvalidIni(String),
+
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
 
@@ -42,6 +59,15 @@ pub struct FileHandler;
 
 #[allow(dead_code)]
 impl FileHandler {
+    /// A handle whose `read` keeps up to `capacity` files in memory, evicting
+    /// the least recently used one when full.
+    pub fn with_cache(capacity: usize) -> CachedFileHandler {
+        CachedFileHandler {
+            capacity,
+            state: Mutex::new(ReadCache::default()),
+        }
+    }
+
     pub fn read<P: AsRef<Path>>(path: P) -> Result<String> {
         let path = path.as_ref();
         if !path.exists() {
@@ -60,6 +86,27 @@ impl FileHandler {
         fs::write(path, content).with_context(|| format!("Failed to write file: {:?}", path))
     }
 
+    /// Expands `~` and `$VAR`/`${VAR}` references in a user-supplied path, e.g.
+    /// `$HOME/reports/out.json`. An undefined variable is an error rather than
+    /// being left in the path; a `$` that does not start a variable name stays as is.
+    pub fn expand_path(path: &str) -> Result<PathBuf> {
+        let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+        expand_path_with(path, home.ok(), |name| std::env::var(name).ok())
+    }
+
+    /// Creates (or truncates) `path` and its parent dirs and returns a buffered
+    /// writer for streaming output. Call `flush` before dropping to see write errors.
+    pub fn open_writer<P: AsRef<Path>>(path: P) -> Result<BufWriter<File>> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file =
+            File::create(path).with_context(|| format!("Failed to write file: {:?}", path))?;
+        Ok(BufWriter::with_capacity(Self::buffer_size(), file))
+    }
+
     pub fn read_json<T, P>(path: P) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
@@ -69,6 +116,29 @@ impl FileHandler {
         serde_json::from_str(&content).map_err(|e| FileError::InvalidJson(e.to_string()).into())
     }
 
+    /// Reads optional config: a missing file yields `T::default()`, while a file
+    /// that exists but fails to parse is still an error.
+    pub fn read_json_or<T, P>(path: P) -> Result<T>
+    where
+        T: Default + for<'de> Deserialize<'de>,
+        P: AsRef<Path>,
+    {
+        if !path.as_ref().exists() {
+            return Ok(T::default());
+        }
+        Self::read_json(path)
+    }
+
+    pub fn read_jsonc<T, P>(path: P) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+        P: AsRef<Path>,
+    {
+        let content = Self::read(&path)?;
+        let json = strip_jsonc(&content);
+        serde_json::from_str(&json).map_err(|e| FileError::InvalidJson(e.to_string()).into())
+    }
+
     pub fn write_json<T, P>(path: P, data: &T, pretty: bool) -> Result<()>
     where
         T: Serialize,
@@ -101,6 +171,208 @@ impl FileHandler {
         Self::write(path, &yaml)
     }
 
+    pub fn merge_yaml<P, Q>(base: P, overlay: Q) -> Result<serde_yaml::Value>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let (base, overlay_path) = (base.as_ref(), overlay.as_ref());
+        let mut merged: serde_yaml::Value = Self::read_yaml(base)?;
+        let overlay: serde_yaml::Value = Self::read_yaml(overlay_path)?;
+
+        for (path, value) in [(base, &merged), (overlay_path, &overlay)] {
+            if !value.is_mapping() {
+                bail!(FileError::InvalidYaml(format!(
+                    "{}: root must be a mapping to merge",
+                    path.display()
+                )));
+            }
+        }
+
+        merge_yaml_values(&mut merged, overlay);
+        Ok(merged)
+    }
+
+    /// Reads `base_path`, deep-merges `{stem}.{environment}.{ext}` beside it when
+    /// that file exists (e.g. `config.production.yml`), then deserializes.
+    pub fn read_config_layered<T, P>(base_path: P, environment: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+        P: AsRef<Path>,
+    {
+        let base_path = base_path.as_ref();
+        let stem = base_path.file_stem().unwrap_or_default().to_string_lossy();
+        let overlay_name = match base_path.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, environment, ext.to_string_lossy()),
+            None => format!("{}.{}", stem, environment),
+        };
+        let overlay_path = base_path.with_file_name(overlay_name);
+
+        let config = if overlay_path.exists() {
+            Self::merge_yaml(base_path, &overlay_path)?
+        } else {
+            Self::read_yaml(base_path)?
+        };
+        serde_yaml::from_value(config)
+            .map_err(|e| FileError::InvalidYaml(format!("{}: {}", base_path.display(), e)).into())
+    }
+
+    /// Reads an INI file as section -> key -> value; keys before any section land in `""`.
+    pub fn read_ini<P: AsRef<Path>>(path: P) -> Result<HashMap<String, HashMap<String, String>>> {
+        let content = Self::read(&path)?;
+        let ini =
+            ini::Ini::load_from_str(&content).map_err(|e| FileError::InvalidIni(e.to_string()))?;
+
+        let mut data: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for (section, properties) in ini.iter() {
+            let entries = data.entry(section.unwrap_or("").to_string()).or_default();
+            for (key, value) in properties.iter() {
+                entries.insert(key.to_string(), value.to_string());
+            }
+        }
+        data.retain(|section, entries| !section.is_empty() || !entries.is_empty());
+
+        Ok(data)
+    }
+
+    pub fn write_ini<P: AsRef<Path>>(
+        path: P,
+        data: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<()> {
+        let mut ini = ini::Ini::new();
+
+        let mut sections: Vec<_> = data.keys().collect();
+        sections.sort();
+        for section in sections {
+            let name = (!section.is_empty()).then(|| section.clone());
+            let mut keys: Vec<_> = data[section].iter().collect();
+            keys.sort();
+            for (key, value) in keys {
+                ini.with_section(name.clone())
+                    .set(key.clone(), value.clone());
+            }
+        }
+
+        let mut output = Vec::new();
+        ini.write_to(&mut output)?;
+        Self::write_bytes(path, &output)
+    }
+
+    pub fn read_base64<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let content = Self::read(&path)?;
+        let encoded: String = content.split_whitespace().collect();
+
+        BASE64
+            .decode(encoded)
+            .map_err(|e| FileError::OperationFailed(format!("Invalid base64: {}", e)).into())
+    }
+
+    pub fn write_base64<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
+        Self::write(path, &BASE64.encode(data))
+    }
+
+    pub fn write_gzip<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        Self::write_bytes(path, &encoder.finish()?)
+    }
+
+    pub fn read_gzip<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let compressed = Self::read_bytes(&path)?;
+        let mut data = Vec::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut data)
+            .map_err(|e| FileError::OperationFailed(format!("Invalid gzip data: {}", e)))?;
+        Ok(data)
+    }
+
+    /// Compresses with zstd at `level` (1-22; 0 selects the library default).
+    pub fn write_zstd<P: AsRef<Path>>(path: P, data: &[u8], level: i32) -> Result<()> {
+        let compressed = zstd::encode_all(data, level)?;
+        Self::write_bytes(path, &compressed)
+    }
+
+    pub fn read_zstd<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let compressed = Self::read_bytes(&path)?;
+        zstd::decode_all(compressed.as_slice())
+            .map_err(|e| FileError::OperationFailed(format!("Invalid zstd data: {}", e)).into())
+    }
+
+    /// Compresses `data` with the codec implied by the path's extension (`.gz`, `.zst`, `.bz2`).
+    pub fn compress_auto<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
+        let path = path.as_ref();
+        match Codec::from_extension(path)? {
+            Codec::Gzip => Self::write_gzip(path, data),
+            Codec::Zstd => Self::write_zstd(path, data, 0),
+            Codec::Bzip2 => {
+                let mut encoder = BzEncoder::new(Vec::new(), bzip2::Compression::default());
+                encoder.write_all(data)?;
+                Self::write_bytes(path, &encoder.finish()?)
+            }
+        }
+    }
+
+    /// Decompresses a file, detecting the codec from magic bytes and falling back to the extension.
+    pub fn decompress_auto<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let path = path.as_ref();
+        let compressed = Self::read_bytes(path)?;
+        let codec = match Codec::from_magic(&compressed) {
+            Some(codec) => codec,
+            None => Codec::from_extension(path)?,
+        };
+
+        let mut data = Vec::new();
+        let result = match codec {
+            Codec::Gzip => GzDecoder::new(compressed.as_slice()).read_to_end(&mut data),
+            Codec::Zstd => zstd::stream::read::Decoder::new(compressed.as_slice())
+                .and_then(|mut decoder| decoder.read_to_end(&mut data)),
+            Codec::Bzip2 => BzDecoder::new(compressed.as_slice()).read_to_end(&mut data),
+        };
+        result
+            .map_err(|e| FileError::OperationFailed(format!("Invalid {:?} data: {}", codec, e)))?;
+
+        Ok(data)
+    }
+
+    /// Classifies a file by sniffing its first bytes (binary signatures, then
+    /// JSON/YAML text markers) and falls back to the extension when the content
+    /// is inconclusive.
+    pub fn classify<P: AsRef<Path>>(path: P) -> Result<FileKind> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let mut head = Vec::new();
+        File::open(path)?
+            .take(CLASSIFY_SNIFF_BYTES)
+            .read_to_end(&mut head)?;
+        let complete = (head.len() as u64) < CLASSIFY_SNIFF_BYTES;
+
+        if let Some(kind) = FileKind::from_content(&head, complete) {
+            return Ok(kind);
+        }
+        Ok(FileKind::from_extension(path))
+    }
+
+    fn read_bytes<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        fs::read(path).with_context(|| format!("Failed to read file: {:?}", path))
+    }
+
+    fn write_bytes<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, data).with_context(|| format!("Failed to write file: {:?}", path))
+    }
+
     pub fn read_csv<P>(path: P) -> Result<Vec<HashMap<String, String>>>
     where
         P: AsRef<Path>,
@@ -124,7 +396,90 @@ impl FileHandler {
         Ok(records)
     }
 
+    /// Deserializes at most the first `n` records and stops reading: CSV rows
+    /// (by header) for `.csv` files, otherwise one JSON value per non-empty
+    /// NDJSON line. Fewer records are returned when the file is shorter.
+    pub fn peek_records<T, P>(path: P, n: usize) -> Result<Vec<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+        let file = File::open(path)?;
+
+        if FileKind::from_extension(path) == FileKind::Csv {
+            return Reader::from_reader(file)
+                .deserialize()
+                .take(n)
+                .map(|record| record.map_err(|e| FileError::InvalidCsv(e.to_string()).into()))
+                .collect();
+        }
+
+        let mut records = Vec::with_capacity(n);
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            if records.len() == n {
+                break;
+            }
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line)
+                .map_err(|e| FileError::InvalidJson(format!("line {}: {}", index + 1, e)))?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Streams CSV rows as header -> field maps to `on_record` without loading
+    /// the whole file. Returns the number of records read.
+    pub fn for_each_csv_record<P, F>(
+        path: P,
+        options: &LineOptions,
+        mut on_record: F,
+    ) -> Result<usize>
+    where
+        P: AsRef<Path>,
+        F: FnMut(HashMap<String, String>) -> Result<()>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let mut reader = Reader::from_reader(LineLimit::new(File::open(path)?, options));
+        let headers = reader.headers().map_err(csv_line_error)?.clone();
+
+        let mut count = 0;
+        for result in reader.records() {
+            let record = result.map_err(csv_line_error)?;
+            on_record(
+                headers
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(h, f)| (h.to_string(), f.to_string()))
+                    .collect(),
+            )?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
     pub fn write_csv<P>(path: P, data: &[HashMap<String, String>]) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Self::write_csv_with(path, data, &CsvOptions::default())
+    }
+
+    pub fn write_csv_with<P>(
+        path: P,
+        data: &[HashMap<String, String>],
+        options: &CsvOptions,
+    ) -> Result<()>
     where
         P: AsRef<Path>,
     {
@@ -132,8 +487,20 @@ impl FileHandler {
             return Self::write(path, "");
         }
 
-        let file = File::create(path.as_ref())?;
-        let mut writer = Writer::from_writer(file);
+        let mut file = File::create(path.as_ref())?;
+        if options.write_bom {
+            file.write_all(UTF8_BOM)?;
+        }
+
+        let terminator = if options.crlf {
+            Terminator::CRLF
+        } else {
+            Terminator::Any(b'\n')
+        };
+        let mut writer = WriterBuilder::new()
+            .delimiter(options.delimiter)
+            .terminator(terminator)
+            .from_writer(file);
 
         // Write headers
         let headers: Vec<_> = data[0].keys().cloned().collect();
@@ -172,6 +539,120 @@ impl FileHandler {
         Ok(())
     }
 
+    /// Copies like `copy`, then sets the destination's modification and access
+    /// times to the source's so backups keep their original timestamps.
+    pub fn copy_preserving<P, Q>(source: P, destination: Q) -> Result<()>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let (source, destination) = (source.as_ref(), destination.as_ref());
+        Self::copy(source, destination)?;
+
+        let metadata = fs::metadata(source)?;
+        filetime::set_file_times(
+            destination,
+            FileTime::from_last_access_time(&metadata),
+            FileTime::from_last_modification_time(&metadata),
+        )?;
+        Ok(())
+    }
+
+    /// Sets the chunk size used by streaming reads, copies and checksums for the
+    /// rest of the process (64 KiB by default). Zero restores the default.
+    pub fn set_buffer_size(bytes: usize) {
+        let bytes = if bytes == 0 { COPY_BUFFER_SIZE } else { bytes };
+        BUFFER_SIZE.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Chunk size currently used by streaming reads, copies and checksums.
+    pub fn buffer_size() -> usize {
+        BUFFER_SIZE.load(Ordering::Relaxed)
+    }
+
+    /// Copies in chunks, calling `on_progress` after each one with a transfer rate
+    /// smoothed over the last few samples. Returns the number of bytes copied.
+    pub fn copy_with_progress<P, Q, F>(source: P, destination: Q, on_progress: F) -> Result<u64>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(&CopyProgress),
+    {
+        Self::copy_with_progress_buffered(source, destination, Self::buffer_size(), on_progress)
+    }
+
+    /// `copy_with_progress` reading `buffer_size` bytes at a time; `on_progress`
+    /// runs once per chunk.
+    pub fn copy_with_progress_buffered<P, Q, F>(
+        source: P,
+        destination: Q,
+        buffer_size: usize,
+        mut on_progress: F,
+    ) -> Result<u64>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(&CopyProgress),
+    {
+        let source = source.as_ref();
+        let destination = destination.as_ref();
+
+        if !source.exists() {
+            bail!(FileError::NotFound(source.display().to_string()));
+        }
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let total = fs::metadata(source)?.len();
+        let mut reader = File::open(source)?;
+        let mut writer = File::create(destination)?;
+        let mut buffer = vec![0u8; buffer_size.max(1)];
+
+        let start = Instant::now();
+        let mut samples: VecDeque<(Instant, u64)> = VecDeque::from([(start, 0)]);
+        let mut bytes = 0u64;
+
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            bytes += read as u64;
+
+            let now = Instant::now();
+            samples.push_back((now, bytes));
+            if samples.len() > RATE_SAMPLES {
+                samples.pop_front();
+            }
+
+            let (oldest_at, oldest_bytes) = samples[0];
+            let window = now.duration_since(oldest_at).as_secs_f64();
+            let rate_bytes_s = if window > 0.0 {
+                (bytes - oldest_bytes) as f64 / window
+            } else {
+                0.0
+            };
+            let eta = if rate_bytes_s > 0.0 {
+                Duration::from_secs_f64(total.saturating_sub(bytes) as f64 / rate_bytes_s)
+            } else {
+                Duration::ZERO
+            };
+
+            on_progress(&CopyProgress {
+                bytes,
+                total,
+                rate_bytes_s,
+                eta,
+            });
+        }
+
+        writer.flush()?;
+        Ok(bytes)
+    }
+
     pub fn move_file<P, Q>(source: P, destination: Q) -> Result<()>
     where
         P: AsRef<Path>,
@@ -188,77 +669,789 @@ impl FileHandler {
             fs::create_dir_all(parent)?;
         }
 
-        fs::rename(source, destination)?;
+        rename_across_devices(source, destination)?;
         Ok(())
     }
 
-    pub fn delete<P: AsRef<Path>>(path: P) -> Result<bool> {
-        let path = path.as_ref();
-        if !path.exists() {
-            return Ok(false);
+    /// Renames files in `dir` whose names match `from`, expanding `$1`-style captures
+    /// into `to_template`. All targets are validated before anything is renamed, and
+    /// if a rename still fails the ones already done are undone in reverse order.
+    pub fn rename_batch<P: AsRef<Path>>(
+        dir: P,
+        from: &Regex,
+        to_template: &str,
+    ) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            bail!(FileError::NotFound(dir.display().to_string()));
         }
 
-        fs::remove_file(path)?;
-        Ok(true)
+        let mut names: Vec<String> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        let renames: Vec<(PathBuf, PathBuf)> = names
+            .iter()
+            .filter(|name| from.is_match(name))
+            .map(|name| {
+                let target = from.replace(name, to_template);
+                (dir.join(name), dir.join(target.as_ref()))
+            })
+            .filter(|(source, target)| source != target)
+            .collect();
+
+        let sources: HashSet<&PathBuf> = renames.iter().map(|(source, _)| source).collect();
+        let mut targets = HashSet::new();
+        for (source, target) in &renames {
+            if !targets.insert(target) {
+                bail!(FileError::OperationFailed(format!(
+                    "Multiple files would be renamed to {}",
+                    target.display()
+                )));
+            }
+            if target.exists() && !sources.contains(target) {
+                bail!(FileError::OperationFailed(format!(
+                    "Renaming {} would overwrite existing {}",
+                    source.display(),
+                    target.display()
+                )));
+            }
+        }
+
+        // Go through temporary names when a target is also a source, so chains don't clobber.
+        let steps: Vec<(PathBuf, PathBuf)> =
+            if renames.iter().any(|(_, target)| sources.contains(target)) {
+                let staged: Vec<PathBuf> = renames
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (source, _))| {
+                        source.with_file_name(format!(".rename_batch.{}.{}", std::process::id(), i))
+                    })
+                    .collect();
+                let to_staged = renames
+                    .iter()
+                    .zip(&staged)
+                    .map(|((source, _), temp)| (source.clone(), temp.clone()));
+                let to_target = renames
+                    .iter()
+                    .zip(&staged)
+                    .map(|((_, target), temp)| (temp.clone(), target.clone()));
+                to_staged.chain(to_target).collect()
+            } else {
+                renames.clone()
+            };
+
+        for (done, (from, to)) in steps.iter().enumerate() {
+            if let Err(e) = fs::rename(from, to) {
+                for (undo_from, undo_to) in steps[..done].iter().rev() {
+                    let _ = fs::rename(undo_to, undo_from);
+                }
+                return Err(e).with_context(|| {
+                    format!("Failed to rename {} to {}", from.display(), to.display())
+                });
+            }
+        }
+
+        Ok(renames)
     }
 
-    pub fn exists<P: AsRef<Path>>(path: P) -> bool {
-        path.as_ref().exists()
+    /// Splits a file into `chunk_size`-byte parts named `<name>.part_0001`, written beside
+    /// the source or into `output_dir` (created if needed). Part numbers are padded to
+    /// at least four digits, wider when the part count needs it, so the parts always
+    /// sort in order by name.
+    pub fn split<P: AsRef<Path>>(
+        path: P,
+        chunk_size: u64,
+        output_dir: Option<&Path>,
+    ) -> Result<Vec<PathBuf>> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+        if chunk_size == 0 {
+            bail!(FileError::OperationFailed(
+                "Chunk size must be greater than zero".to_string()
+            ));
+        }
+
+        let target_dir = match output_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        };
+        fs::create_dir_all(&target_dir)?;
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut source = File::open(path)?;
+        let width = part_number_width(source.metadata()?.len().div_ceil(chunk_size));
+        let mut parts = Vec::new();
+
+        loop {
+            let mut chunk = Vec::new();
+            (&mut source).take(chunk_size).read_to_end(&mut chunk)?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let part = target_dir.join(format!(
+                "{}.part_{:0width$}",
+                file_name,
+                parts.len() + 1,
+                width = width
+            ));
+            fs::write(&part, &chunk)?;
+            parts.push(part);
+        }
+
+        Ok(parts)
     }
 
-    pub fn size<P: AsRef<Path>>(path: P) -> Result<u64> {
+    /// Sets the file length to `size`, shrinking it or padding with zero bytes.
+    pub fn truncate<P: AsRef<Path>>(path: P, size: u64) -> Result<()> {
         let path = path.as_ref();
         if !path.exists() {
             bail!(FileError::NotFound(path.display().to_string()));
         }
 
-        let metadata = fs::metadata(path)?;
-        Ok(metadata.len())
+        File::options().write(true).open(path)?.set_len(size)?;
+        Ok(())
     }
 
-    pub fn checksum<P: AsRef<Path>>(path: P, algorithm: &str) -> Result<String> {
+    /// Creates an empty file in the system temp dir named `{prefix}{random}{suffix}`.
+    /// The returned guard derefs to its `Path` and deletes the file when dropped.
+    pub fn temp_file(prefix: &str, suffix: &str) -> Result<TempPath> {
+        let file = tempfile::Builder::new()
+            .prefix(prefix)
+            .suffix(suffix)
+            .tempfile()
+            .map_err(|e| {
+                FileError::WriteError(std::env::temp_dir().display().to_string(), e.to_string())
+            })?;
+        Ok(file.into_temp_path())
+    }
+
+    pub fn delete<P: AsRef<Path>>(path: P) -> Result<bool> {
         let path = path.as_ref();
         if !path.exists() {
-            bail!(FileError::NotFound(path.display().to_string()));
+            return Ok(false);
         }
 
-        let mut file = File::open(path)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)?;
+        fs::remove_file(path)?;
+        Ok(true)
+    }
 
-        let hash = match algorithm {
-            "md5" => {
-                let digest = md5::compute(&buffer);
-                format!("{:x}", digest)
-            }
-            "sha1" => {
-                let mut hasher = Sha1::new();
-                hasher.update(&buffer);
-                format!("{:x}", hasher.finalize())
-            }
-            "sha256" => {
-                let mut hasher = Sha256::new();
-                hasher.update(&buffer);
-                format!("{:x}", hasher.finalize())
+    /// Deletes regular files in `dir` last modified more than `age` ago and returns
+    /// them. Subdirectories are only searched when `recursive`; directories and
+    /// symlinks themselves are never removed.
+    pub fn purge_older_than<P: AsRef<Path>>(
+        dir: P,
+        age: Duration,
+        recursive: bool,
+    ) -> Result<Vec<PathBuf>> {
+        let options = WalkOptions {
+            max_depth: if recursive { None } else { Some(1) },
+            ..Default::default()
+        };
+        let cutoff = std::time::SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(std::time::UNIX_EPOCH);
+
+        let mut removed = Vec::new();
+        for entry in Self::walk_dir(dir, &options)? {
+            let metadata = fs::symlink_metadata(&entry)?;
+            if metadata.is_file() && metadata.modified()? < cutoff {
+                fs::remove_file(&entry)?;
+                removed.push(entry);
             }
-            "sha512" => {
-                let mut hasher = Sha512::new();
-                hasher.update(&buffer);
-                format!("{:x}", hasher.finalize())
+        }
+        Ok(removed)
+    }
+
+    /// Among the regular files directly in `dir` whose names match `pattern`,
+    /// deletes all but the `keep` most recently modified and returns the deleted.
+    pub fn retain_newest<P: AsRef<Path>>(
+        dir: P,
+        pattern: &Regex,
+        keep: usize,
+    ) -> Result<Vec<PathBuf>> {
+        let mut candidates = Vec::new();
+        for entry in Self::list_dir(dir, &WalkOptions::default(), SortBy::Name, false)? {
+            let metadata = fs::symlink_metadata(&entry)?;
+            let matches = entry
+                .file_name()
+                .is_some_and(|name| pattern.is_match(&name.to_string_lossy()));
+            if metadata.is_file() && matches {
+                candidates.push((metadata.modified()?, entry));
             }
-            _ => bail!("Unsupported algorithm: {}", algorithm),
+        }
+
+        // Newest first; equal mtimes keep the later name.
+        candidates.sort_by(|a, b| b.cmp(a));
+        let mut deleted = Vec::new();
+        for (_, entry) in candidates.into_iter().skip(keep) {
+            fs::remove_file(&entry)?;
+            deleted.push(entry);
+        }
+        Ok(deleted)
+    }
+
+    pub fn walk_dir<P: AsRef<Path>>(path: P, options: &WalkOptions) -> Result<Vec<PathBuf>> {
+        let mut entries = Vec::new();
+        Self::walk_dir_with_progress(path, options, |entry| {
+            entries.push(entry.to_path_buf());
+            true
+        })?;
+        Ok(entries)
+    }
+
+    /// Lists the direct children of `path` ordered by `sort`, largest/newest/last
+    /// name first when `descending`. Ties fall back to ascending name order
+    /// either way.
+    pub fn list_dir<P: AsRef<Path>>(
+        path: P,
+        options: &WalkOptions,
+        sort: SortBy,
+        descending: bool,
+    ) -> Result<Vec<PathBuf>> {
+        let options = WalkOptions {
+            max_depth: Some(1),
+            ..options.clone()
         };
+        let mut entries = Self::walk_dir(path, &options)?;
 
-        Ok(hash)
+        match sort {
+            SortBy::Name => {
+                entries.sort();
+                if descending {
+                    entries.reverse();
+                }
+            }
+            SortBy::Size => sort_by_primary(&mut entries, descending, |entry| {
+                fs::metadata(entry).map_or(0, |m| m.len())
+            }),
+            SortBy::Mtime => sort_by_primary(&mut entries, descending, |entry| {
+                let modified = fs::metadata(entry).and_then(|m| m.modified());
+                modified.unwrap_or(std::time::UNIX_EPOCH)
+            }),
+        }
+        Ok(entries)
     }
 
-    pub fn stats<P: AsRef<Path>>(path: P) -> Result<FileStats> {
+    /// Walks like `walk_dir` but hands each entry to `on_entry` as it is found
+    /// instead of collecting them. Returning false from the callback stops the
+    /// walk early. Returns the number of entries visited.
+    pub fn walk_dir_with_progress<P, F>(
+        path: P,
+        options: &WalkOptions,
+        mut on_entry: F,
+    ) -> Result<usize>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Path) -> bool,
+    {
         let path = path.as_ref();
-        if !path.exists() {
+        if !path.is_dir() {
             bail!(FileError::NotFound(path.display().to_string()));
         }
 
-        let metadata = fs::metadata(path)?;
+        let mut visited = HashSet::new();
+        if options.follow_symlinks {
+            visited.insert(dir_id(path)?);
+        }
+        let mut count = 0;
+        Self::walk_into(path, 1, options, &mut visited, &mut |entry| {
+            count += 1;
+            on_entry(entry)
+        })?;
+        Ok(count)
+    }
+
+    /// Returns false once `on_entry` has asked to stop.
+    fn walk_into(
+        dir: &Path,
+        depth: usize,
+        options: &WalkOptions,
+        visited: &mut HashSet<DirId>,
+        on_entry: &mut dyn FnMut(&Path) -> bool,
+    ) -> Result<bool> {
+        if options.max_depth.is_some_and(|max| depth > max) {
+            return Ok(true);
+        }
+
+        let mut children: Vec<_> = fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+        children.sort_by_key(|entry| entry.file_name());
+
+        for child in children {
+            if !options.include_hidden && is_hidden(&child) {
+                continue;
+            }
+
+            let child_path = child.path();
+            let file_type = child.file_type()?;
+            let mut is_dir = file_type.is_dir();
+            if file_type.is_symlink() {
+                if !options.follow_symlinks {
+                    continue;
+                }
+                // Dangling links have nothing to follow.
+                let Ok(target) = fs::metadata(&child_path) else {
+                    continue;
+                };
+                is_dir = target.is_dir();
+            }
+
+            if !on_entry(&child_path) {
+                return Ok(false);
+            }
+
+            // Only descend into directories not already seen, so link cycles terminate.
+            if is_dir
+                && (!options.follow_symlinks || visited.insert(dir_id(&child_path)?))
+                && !Self::walk_into(&child_path, depth + 1, options, visited, on_entry)?
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Total size in bytes of the files under `path`, honouring the walk options.
+    pub fn dir_size<P: AsRef<Path>>(path: P, options: &WalkOptions) -> Result<u64> {
+        let mut total = 0;
+        for entry in Self::walk_dir(path, options)? {
+            let metadata = fs::metadata(&entry)?;
+            if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn find<P: AsRef<Path>>(
+        path: P,
+        pattern: &str,
+        options: &WalkOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let pattern = glob::Pattern::new(pattern)
+            .map_err(|e| FileError::OperationFailed(format!("Invalid pattern: {}", e)))?;
+
+        Ok(Self::walk_dir(path, options)?
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+            })
+            .collect())
+    }
+
+    pub fn glob(pattern: &str) -> Result<Vec<PathBuf>> {
+        let paths = glob::glob(pattern)
+            .map_err(|e| FileError::OperationFailed(format!("Invalid pattern: {}", e)))?;
+
+        let mut matches = paths.collect::<std::result::Result<Vec<_>, _>>()?;
+        matches.sort();
+        Ok(matches)
+    }
+
+    pub fn exists<P: AsRef<Path>>(path: P) -> bool {
+        path.as_ref().exists()
+    }
+
+    pub fn size<P: AsRef<Path>>(path: P) -> Result<u64> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let metadata = fs::metadata(path)?;
+        Ok(metadata.len())
+    }
+
+    /// Reads the file as lines without their terminators.
+    pub fn read_lines<P: AsRef<Path>>(path: P, options: &LineOptions) -> Result<Vec<String>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let reader = BufReader::new(LineLimit::new(File::open(path)?, options));
+        reader
+            .lines()
+            .map(|line| line.map_err(line_error))
+            .collect()
+    }
+
+    /// Returns the lines of the file matching `pattern` (or not matching, with
+    /// `invert_match`), reading one line at a time so large logs are searched
+    /// without loading them whole.
+    pub fn grep<P: AsRef<Path>>(
+        path: P,
+        pattern: &Regex,
+        options: &GrepOptions,
+    ) -> Result<Vec<GrepMatch>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        Self::grep_reader(File::open(path)?, pattern, options)
+    }
+
+    /// `grep` over any reader. Invalid UTF-8 is replaced rather than rejected.
+    pub fn grep_reader<R: Read>(
+        reader: R,
+        pattern: &Regex,
+        options: &GrepOptions,
+    ) -> Result<Vec<GrepMatch>> {
+        let mut matches = Vec::new();
+        grep_lines(reader, pattern, options, |found| {
+            matches.push(found);
+            Ok(())
+        })?;
+        Ok(matches)
+    }
+
+    /// Like `grep`, but hands each selected line to `on_match` as soon as it is
+    /// read, so memory stays flat however many lines are selected. An error
+    /// from `on_match` stops the scan.
+    pub fn grep_each<P, F>(
+        path: P,
+        pattern: &Regex,
+        options: &GrepOptions,
+        on_match: F,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(GrepMatch) -> Result<()>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        grep_lines(File::open(path)?, pattern, options, on_match)
+    }
+
+    /// Like `grep`, but each selected line comes with up to `before` preceding
+    /// and `after` following lines. Overlapping or adjacent windows merge into
+    /// one group; only the last `before` lines are held while scanning.
+    pub fn grep_context<P: AsRef<Path>>(
+        path: P,
+        pattern: &Regex,
+        options: &GrepOptions,
+        before: usize,
+        after: usize,
+    ) -> Result<Vec<GrepGroup>> {
+        let mut groups: Vec<GrepGroup> = Vec::new();
+        Self::grep_context_each(path, pattern, options, before, after, |entry, new_group| {
+            match groups.last_mut() {
+                Some(group) if !new_group => group.lines.push(entry),
+                _ => groups.push(GrepGroup { lines: vec![entry] }),
+            }
+            Ok(())
+        })?;
+        Ok(groups)
+    }
+
+    /// Streaming `grep_context`: each output line goes to `on_line` as soon as
+    /// it is known to belong to a group, with `true` for the first line of each
+    /// group. An error from `on_line` stops the scan.
+    pub fn grep_context_each<P, F>(
+        path: P,
+        pattern: &Regex,
+        options: &GrepOptions,
+        before: usize,
+        after: usize,
+        mut on_line: F,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(GrepLine, bool) -> Result<()>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let mut last_emitted: Option<usize> = None;
+        let mut history: VecDeque<GrepLine> = VecDeque::with_capacity(before);
+        let mut after_remaining = 0;
+
+        for_each_line(File::open(path)?, &options.lines, |line_number, line| {
+            let is_match = options.selects(pattern, line);
+            let entry = GrepLine {
+                line_number,
+                line: line.to_string(),
+                is_match,
+            };
+
+            if is_match {
+                let first = history.front().map_or(line_number, |l| l.line_number);
+                let mut new_group = last_emitted.is_none_or(|last| last + 1 != first);
+                for previous in history.drain(..) {
+                    on_line(previous, new_group)?;
+                    new_group = false;
+                }
+                on_line(entry, new_group)?;
+                last_emitted = Some(line_number);
+                after_remaining = after;
+            } else if after_remaining > 0 {
+                on_line(entry, false)?;
+                last_emitted = Some(line_number);
+                after_remaining -= 1;
+            } else if before > 0 {
+                if history.len() == before {
+                    history.pop_front();
+                }
+                history.push_back(entry);
+            }
+            Ok(())
+        })
+    }
+
+    /// Number of lines matching `pattern`, without collecting the lines themselves.
+    pub fn grep_count<P: AsRef<Path>>(
+        path: P,
+        pattern: &Regex,
+        options: &GrepOptions,
+    ) -> Result<usize> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let mut count = 0;
+        for_each_line(File::open(path)?, &options.lines, |_, line| {
+            if options.selects(pattern, line) {
+                count += 1;
+            }
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Line-by-line difference between two text files, in file order.
+    pub fn diff<P, Q>(old: P, new: Q) -> Result<Vec<DiffLine>>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let (old, new) = (Self::read(old)?, Self::read(new)?);
+        Ok(similar::TextDiff::from_lines(&old, &new)
+            .iter_all_changes()
+            .map(|change| {
+                let line = change.value().trim_end_matches(['\n', '\r']).to_string();
+                match change.tag() {
+                    similar::ChangeTag::Equal => DiffLine::Same(line),
+                    similar::ChangeTag::Insert => DiffLine::Added(line),
+                    similar::ChangeTag::Delete => DiffLine::Removed(line),
+                }
+            })
+            .collect())
+    }
+
+    pub fn checksum<P: AsRef<Path>>(path: P, algorithm: &str) -> Result<String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        Self::checksum_reader(File::open(path)?, algorithm)
+    }
+
+    /// `path` relative to `root`, joined with `/` on every platform, so listings
+    /// written on one OS compare equal on another.
+    pub fn relative_slash_path(root: &Path, path: &Path) -> String {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Single digest for a whole tree: every file is hashed (in parallel), the
+    /// `hash  relative/path` lines are sorted and joined, and that listing is
+    /// hashed again. Adding, removing, renaming or editing any file changes the
+    /// root, while traversal order does not.
+    pub fn tree_hash<P: AsRef<Path>>(dir: P, algorithm: &str) -> Result<String> {
+        let dir = dir.as_ref();
+        let mut files: Vec<(String, PathBuf)> = Self::walk_dir(dir, &WalkOptions::default())?
+            .into_iter()
+            .filter(|path| path.is_file())
+            .map(|path| (Self::relative_slash_path(dir, &path), path))
+            .collect();
+        files.sort();
+
+        let digests = parallel::map_ordered(&files, 0, |(_, path)| Self::checksum(path, algorithm));
+        let mut listing = String::new();
+        for ((name, _), digest) in files.iter().zip(digests) {
+            listing.push_str(&format!("{}  {}\n", digest?, name));
+        }
+        Self::checksum_bytes(listing.as_bytes(), algorithm)
+    }
+
+    /// Copies `file` into a content-addressable store as `store_dir/ab/cdef...`
+    /// (sharded by the first two hex digits of its digest) and returns the
+    /// digest. Storing content that is already present leaves the store untouched.
+    pub fn cas_store<P, Q>(file: P, store_dir: Q, algorithm: &str) -> Result<String>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        let digest = Self::checksum(&file, algorithm)?;
+        let target = cas_path(store_dir.as_ref(), &digest)?;
+        if target.exists() {
+            return Ok(digest);
+        }
+
+        let shard = target.parent().unwrap_or(store_dir.as_ref());
+        fs::create_dir_all(shard)?;
+        let temp_path = shard.join(format!(".{}.tmp.{}", digest, std::process::id()));
+        let result =
+            fs::copy(&file, &temp_path).and_then(|_| rename_across_devices(&temp_path, &target));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result?;
+
+        Ok(digest)
+    }
+
+    /// Resolves a digest returned by `cas_store` to the stored file's path.
+    pub fn cas_get<P: AsRef<Path>>(store_dir: P, hash: &str) -> Result<PathBuf> {
+        let path = cas_path(store_dir.as_ref(), hash)?;
+        if !path.is_file() {
+            bail!(FileError::NotFound(format!(
+                "{} in {}",
+                hash,
+                store_dir.as_ref().display()
+            )));
+        }
+        Ok(path)
+    }
+
+    /// Hashes only the first `bytes` of the file (the whole file if it is shorter),
+    /// as a cheap first pass when looking for duplicates among large files.
+    pub fn checksum_prefix<P: AsRef<Path>>(path: P, algorithm: &str, bytes: u64) -> Result<String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        Self::checksum_reader(File::open(path)?.take(bytes), algorithm)
+    }
+
+    pub fn checksum_bytes(data: &[u8], algorithm: &str) -> Result<String> {
+        Self::checksum_reader(data, algorithm)
+    }
+
+    /// Hashes everything `reader` yields in fixed-size chunks, so input of any
+    /// length (files, stdin) is digested without being buffered whole.
+    pub fn checksum_reader<R: Read>(reader: R, algorithm: &str) -> Result<String> {
+        Self::checksum_reader_buffered(reader, algorithm, Self::buffer_size())
+    }
+
+    /// `checksum_reader` with an explicit chunk size instead of the process default.
+    pub fn checksum_reader_buffered<R: Read>(
+        mut reader: R,
+        algorithm: &str,
+        buffer_size: usize,
+    ) -> Result<String> {
+        let hash = match algorithm {
+            "md5" => {
+                let mut context = md5::Context::new();
+                read_chunks(&mut reader, buffer_size, |chunk| context.consume(chunk))?;
+                format!("{:x}", context.finalize())
+            }
+            "sha1" => digest_reader::<Sha1, _>(reader, buffer_size)?,
+            "sha256" => digest_reader::<Sha256, _>(reader, buffer_size)?,
+            "sha512" => digest_reader::<Sha512, _>(reader, buffer_size)?,
+            _ => bail!("Unsupported algorithm: {}", algorithm),
+        };
+
+        Ok(hash)
+    }
+
+    /// Returns the cached digest while the file's mtime and size are unchanged,
+    /// recomputing and updating the cache otherwise.
+    pub fn checksum_cached<P: AsRef<Path>>(
+        path: P,
+        algorithm: &str,
+        cache: &mut HashMap<ChecksumKey, CachedChecksum>,
+    ) -> Result<String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let metadata = fs::metadata(path)?;
+        let (modified, size) = (metadata.modified()?, metadata.len());
+        let key = (path.to_path_buf(), algorithm.to_string());
+        if let Some(cached) = cache.get(&key) {
+            if cached.modified == modified && cached.size == size {
+                return Ok(cached.digest.clone());
+            }
+        }
+
+        let digest = Self::checksum(path, algorithm)?;
+        cache.insert(
+            key,
+            CachedChecksum {
+                modified,
+                size,
+                digest: digest.clone(),
+            },
+        );
+
+        Ok(digest)
+    }
+
+    pub fn verify_checksum<P: AsRef<Path>>(
+        path: P,
+        algorithm: &str,
+        expected: &str,
+    ) -> Result<bool> {
+        let actual = Self::checksum(path, algorithm)?;
+        Ok(actual.eq_ignore_ascii_case(expected.trim()))
+    }
+
+    /// True when `derived` is missing or was modified before `source`.
+    pub fn is_stale<P: AsRef<Path>, Q: AsRef<Path>>(source: P, derived: Q) -> Result<bool> {
+        let source = source.as_ref();
+        if !source.exists() {
+            bail!(FileError::NotFound(source.display().to_string()));
+        }
+
+        let derived = derived.as_ref();
+        if !derived.exists() {
+            return Ok(true);
+        }
+
+        Ok(fs::metadata(derived)?.modified()? < fs::metadata(source)?.modified()?)
+    }
+
+    pub fn stats<P: AsRef<Path>>(path: P) -> Result<FileStats> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        let metadata = fs::metadata(path)?;
+        #[cfg(unix)]
+        let (uid, gid) = {
+            use std::os::unix::fs::MetadataExt;
+            (Some(metadata.uid()), Some(metadata.gid()))
+        };
+        #[cfg(not(unix))]
+        let (uid, gid) = (None, None);
 
         Ok(FileStats {
             size: metadata.len(),
@@ -274,20 +1467,902 @@ impl FileHandler {
             },
             #[cfg(not(unix))]
             permissions: String::from("N/A"),
+            uid,
+            gid,
+            owner: uid.and_then(user_name),
+            group: gid.and_then(group_name),
         })
     }
 
     pub fn atomic_write<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+        Self::atomic_write_in(path, content, None::<&Path>)
+    }
+
+    /// Like `atomic_write`, but stages the temp file in `temp_dir` (the target's
+    /// parent when `None`). The rename only stays atomic on one filesystem.
+    pub fn atomic_write_in<P: AsRef<Path>, D: AsRef<Path>>(
+        path: P,
+        content: &str,
+        temp_dir: Option<D>,
+    ) -> Result<()> {
+        atomic_replace(
+            path.as_ref(),
+            temp_dir.as_ref().map(AsRef::as_ref),
+            |temp| {
+                temp.write_all(content.as_bytes())?;
+                Ok(())
+            },
+        )
+    }
+
+    /// Writes `content` ahead of the existing bytes. The original is streamed
+    /// into a temp file which then replaces it, so readers never see a partial file.
+    pub fn prepend<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+
+        atomic_replace(path, None, |temp| {
+            temp.write_all(content.as_bytes())?;
+            std::io::copy(&mut File::open(path)?, temp)?;
+            Ok(())
+        })
+    }
+
+    /// Rewrites every line terminator as `style` and returns how many lines
+    /// changed. A missing final newline stays missing; unchanged files are not rewritten.
+    pub fn normalize_line_endings<P: AsRef<Path>>(path: P, style: LineEnding) -> Result<usize> {
+        let path = path.as_ref();
+        let content = Self::read(path)?;
+
+        let mut normalized = String::with_capacity(content.len());
+        let mut changed = 0;
+        for line in content.split_inclusive('\n') {
+            let Some(body) = line.strip_suffix('\n') else {
+                normalized.push_str(line);
+                continue;
+            };
+            let body = body.strip_suffix('\r').unwrap_or(body);
+
+            normalized.push_str(body);
+            normalized.push_str(style.as_str());
+            if line.len() - body.len() != style.as_str().len() {
+                changed += 1;
+            }
+        }
+
+        if changed > 0 {
+            Self::atomic_write(path, &normalized)?;
+        }
+        Ok(changed)
+    }
+
+    /// Strips trailing spaces and tabs from each line and/or appends a missing
+    /// final newline. Returns whether the file changed; clean files are left untouched.
+    pub fn fix_whitespace<P: AsRef<Path>>(
+        path: P,
+        trim_trailing: bool,
+        ensure_final_newline: bool,
+    ) -> Result<bool> {
         let path = path.as_ref();
-        let temp_path = path.with_extension(format!("tmp.{}", std::process::id()));
+        let content = Self::read(path)?;
+
+        let mut fixed = String::with_capacity(content.len() + 1);
+        for line in content.split_inclusive('\n') {
+            let body = line.trim_end_matches(['\r', '\n']);
+            let terminator = &line[body.len()..];
+            if trim_trailing {
+                fixed.push_str(body.trim_end_matches([' ', '\t']));
+            } else {
+                fixed.push_str(body);
+            }
+            fixed.push_str(terminator);
+        }
+        if ensure_final_newline && !fixed.is_empty() && !fixed.ends_with('\n') {
+            fixed.push('\n');
+        }
+
+        if fixed == content {
+            return Ok(false);
+        }
+        Self::atomic_write(path, &fixed)?;
+        Ok(true)
+    }
+}
+
+/// Digits needed to zero-pad every part number up to `total`, never fewer than four.
+fn part_number_width(total: u64) -> usize {
+    (total.max(1).ilog10() as usize + 1).max(4)
+}
+
+/// Sorts `entries` by `key`, reversed when `descending`, breaking ties by
+/// ascending path so equal keys always list A→Z.
+fn sort_by_primary<K, F>(entries: &mut Vec<PathBuf>, descending: bool, key: F)
+where
+    K: Ord,
+    F: Fn(&Path) -> K,
+{
+    let mut keyed: Vec<(K, PathBuf)> = entries.drain(..).map(|e| (key(&e), e)).collect();
+    keyed.sort_by(|(key_a, a), (key_b, b)| {
+        let primary = key_a.cmp(key_b);
+        let primary = if descending {
+            primary.reverse()
+        } else {
+            primary
+        };
+        primary.then_with(|| a.cmp(b))
+    });
+    entries.extend(keyed.into_iter().map(|(_, entry)| entry));
+}
+
+/// Fills a `.name.tmp.PID` file in `temp_dir` (the target's parent when `None`)
+/// via `fill`, gives it the permissions of the file it replaces, syncs it and
+/// renames it over `path`. The temp file is removed on any error.
+fn atomic_replace<F>(path: &Path, temp_dir: Option<&Path>, fill: F) -> Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> Result<()>,
+{
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| FileError::OperationFailed(format!("{} has no file name", path.display())))?
+        .to_string_lossy();
+    let temp_name = format!(".{}.tmp.{}", file_name, std::process::id());
+    let temp_dir = temp_dir.unwrap_or_else(|| path.parent().unwrap_or(Path::new("")));
+    let temp_path = temp_dir.join(temp_name);
+
+    let result = (|| -> Result<()> {
+        fs::create_dir_all(temp_dir)?;
+        let mut temp = BufWriter::new(
+            File::create(&temp_path)
+                .with_context(|| format!("Failed to write file: {:?}", temp_path))?,
+        );
+        fill(&mut temp)?;
+        let temp = temp.into_inner().map_err(|e| e.into_error())?;
+        if let Ok(metadata) = fs::metadata(path) {
+            temp.set_permissions(metadata.permissions())?;
+        }
+        temp.sync_all()?;
+        rename_across_devices(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// `fs::rename`, falling back to copy-then-delete when the two paths live on
+/// different filesystems (`EXDEV`), e.g. a target under a separately mounted `/tmp`.
+/// The copy is staged beside the destination and renamed into place, so readers
+/// never see a partially copied file.
+fn rename_across_devices(source: &Path, destination: &Path) -> std::io::Result<()> {
+    rename_with(source, destination, |from, to| fs::rename(from, to))
+}
+
+fn rename_with(
+    source: &Path,
+    destination: &Path,
+    rename: impl Fn(&Path, &Path) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    match rename(source, destination) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices && source.is_file() => {
+            let name = destination
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let staging =
+                destination.with_file_name(format!(".{}.tmp.{}", name, std::process::id()));
+
+            let result = fs::copy(source, &staging)
+                .and_then(|_| File::open(&staging)?.sync_all())
+                .and_then(|()| fs::rename(&staging, destination));
+            if result.is_err() {
+                let _ = fs::remove_file(&staging);
+            }
+            result?;
+            fs::remove_file(source)
+        }
+        result => result,
+    }
+}
+
+/// `FileHandler::expand_path` with the home directory and variable lookup supplied.
+fn expand_path_with<F>(path: &str, home: Option<String>, mut lookup: F) -> Result<PathBuf>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let escaped = escape_literal_dollars(path);
+    let expanded = shellexpand::full_with_context(
+        &escaped,
+        || home,
+        |name| match lookup(name) {
+            Some(value) => Ok(Some(value)),
+            None => Err("not set"),
+        },
+    )
+    .map_err(|e| FileError::OperationFailed(format!("Cannot expand path '{}': {}", path, e)))?;
+    Ok(PathBuf::from(expanded.into_owned()))
+}
+
+/// Doubles every `$` that does not start a `$NAME` or `${NAME}` reference,
+/// which `shellexpand` then reads back as a literal `$`.
+fn escape_literal_dollars(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for (i, c) in path.char_indices() {
+        escaped.push(c);
+        if c == '$' && !starts_variable(&path[i + 1..]) {
+            escaped.push('$');
+        }
+    }
+    escaped
+}
+
+fn starts_variable(rest: &str) -> bool {
+    let is_name_start = |c: char| c.is_ascii_alphabetic() || c == '_';
+    match rest.strip_prefix('{') {
+        Some(braced) => braced.find('}').is_some_and(|end| {
+            let name = &braced[..end];
+            name.starts_with(is_name_start)
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }),
+        None => rest.starts_with(is_name_start),
+    }
+}
 
-        Self::write(&temp_path, content)?;
-        fs::rename(&temp_path, path)?;
+/// Feeds each line (1-based number, terminator stripped, invalid UTF-8
+/// replaced) to `on_line`, enforcing `options.max_line_length`. Stops at the
+/// first error from `on_line`.
+fn for_each_line<R: Read>(
+    reader: R,
+    options: &LineOptions,
+    mut on_line: impl FnMut(usize, &str) -> Result<()>,
+) -> Result<()> {
+    let mut reader =
+        BufReader::with_capacity(FileHandler::buffer_size(), LineLimit::new(reader, options));
+    let mut buffer = Vec::new();
+    let mut line_number = 0;
+
+    while reader.read_until(b'\n', &mut buffer).map_err(line_error)? > 0 {
+        line_number += 1;
+        let line = String::from_utf8_lossy(&buffer);
+        on_line(line_number, line.trim_end_matches(['\n', '\r']))?;
+        buffer.clear();
+    }
+    Ok(())
+}
 
+/// Hands each line selected by `pattern` and `options` to `on_match`.
+fn grep_lines<R: Read>(
+    reader: R,
+    pattern: &Regex,
+    options: &GrepOptions,
+    mut on_match: impl FnMut(GrepMatch) -> Result<()>,
+) -> Result<()> {
+    for_each_line(reader, &options.lines, |line_number, line| {
+        if options.selects(pattern, line) {
+            on_match(GrepMatch {
+                line_number,
+                line: line.to_string(),
+            })?;
+        }
         Ok(())
+    })
+}
+
+fn cas_path(store_dir: &Path, hash: &str) -> Result<PathBuf> {
+    if hash.len() < 3 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!(FileError::OperationFailed(format!(
+            "Invalid content hash '{}'",
+            hash
+        )));
+    }
+    let hash = hash.to_ascii_lowercase();
+    Ok(store_dir.join(&hash[..2]).join(&hash[2..]))
+}
+
+fn read_chunks<R: Read>(
+    reader: &mut R,
+    buffer_size: usize,
+    mut on_chunk: impl FnMut(&[u8]),
+) -> Result<()> {
+    let mut buffer = vec![0u8; buffer_size.max(1)];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => on_chunk(&buffer[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn digest_reader<D: Digest, R: Read>(mut reader: R, buffer_size: usize) -> Result<String> {
+    let mut hasher = D::new();
+    read_chunks(&mut reader, buffer_size, |chunk| hasher.update(chunk))?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Codec {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl Codec {
+    fn from_extension(path: &Path) -> Result<Self> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "gz" | "gzip" => Ok(Codec::Gzip),
+            "zst" | "zstd" => Ok(Codec::Zstd),
+            "bz2" => Ok(Codec::Bzip2),
+            _ => bail!(FileError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Codec::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Codec::Zstd)
+        } else if bytes.starts_with(b"BZh") {
+            Some(Codec::Bzip2)
+        } else {
+            None
+        }
+    }
+}
+
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+static BUFFER_SIZE: AtomicUsize = AtomicUsize::new(COPY_BUFFER_SIZE);
+const RATE_SAMPLES: usize = 5;
+
+/// A run of consecutive lines around one or more `grep_context` matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct GrepGroup {
+    pub lines: Vec<GrepLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct GrepLine {
+    /// 1-based line number within the input.
+    pub line_number: usize,
+    /// The line without its terminator.
+    pub line: String,
+    /// Whether the line was selected, as opposed to shown for context.
+    pub is_match: bool,
+}
+
+/// Matching options for `grep` and `grep_count`.
+#[derive(Debug, Clone, Default)]
+#[allow(dead_code)]
+pub struct GrepOptions {
+    /// Select the lines that do *not* match the pattern.
+    pub invert_match: bool,
+    pub lines: LineOptions,
+}
+
+impl GrepOptions {
+    fn selects(&self, pattern: &Regex, line: &str) -> bool {
+        pattern.is_match(line) != self.invert_match
+    }
+}
+
+/// Limits for line-based reads (`read_lines`, `for_each_csv_record`, `grep`).
+#[derive(Debug, Clone, Default)]
+#[allow(dead_code)]
+pub struct LineOptions {
+    /// Fail with `FileError::OperationFailed` once a line grows past this many
+    /// bytes, so a huge single-line input cannot exhaust memory. `None` is unlimited.
+    pub max_line_length: Option<usize>,
+}
+
+/// Reader adapter that fails as soon as a line exceeds the configured length.
+struct LineLimit<R> {
+    inner: R,
+    max_line_length: Option<usize>,
+    current_length: usize,
+    line_number: usize,
+}
+
+impl<R: Read> LineLimit<R> {
+    fn new(inner: R, options: &LineOptions) -> Self {
+        Self {
+            inner,
+            max_line_length: options.max_line_length,
+            current_length: 0,
+            line_number: 1,
+        }
+    }
+}
+
+impl<R: Read> Read for LineLimit<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let Some(max) = self.max_line_length else {
+            return Ok(read);
+        };
+
+        for &byte in &buf[..read] {
+            match byte {
+                b'\n' => {
+                    self.current_length = 0;
+                    self.line_number += 1;
+                }
+                b'\r' => {}
+                _ => {
+                    self.current_length += 1;
+                    if self.current_length > max {
+                        return Err(std::io::Error::other(FileError::OperationFailed(format!(
+                            "line {} exceeds the maximum length of {} bytes",
+                            self.line_number, max
+                        ))));
+                    }
+                }
+            }
+        }
+        Ok(read)
+    }
+}
+
+/// Unwraps a `FileError` raised by `LineLimit` from the I/O error carrying it.
+fn line_error(e: std::io::Error) -> anyhow::Error {
+    if !e.get_ref().is_some_and(|inner| inner.is::<FileError>()) {
+        return e.into();
+    }
+    match e.into_inner().map(|inner| inner.downcast::<FileError>()) {
+        Some(Ok(file_error)) => (*file_error).into(),
+        Some(Err(other)) => anyhow::anyhow!(other),
+        None => anyhow::anyhow!("I/O error"),
+    }
+}
+
+fn csv_line_error(e: csv::Error) -> anyhow::Error {
+    if !e.is_io_error() {
+        return e.into();
+    }
+    let csv::ErrorKind::Io(e) = e.into_kind() else {
+        unreachable!("checked is_io_error");
+    };
+    line_error(e)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct GrepMatch {
+    /// 1-based line number within the input.
+    pub line_number: usize,
+    /// The line without its terminator.
+    pub line: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CopyProgress {
+    pub bytes: u64,
+    pub total: u64,
+    /// Moving average over the last few chunks, in bytes per second.
+    pub rate_bytes_s: f64,
+    pub eta: Duration,
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    /// Terminate rows with `\r\n` instead of `\n`, as Excel on Windows expects.
+    pub crlf: bool,
+    /// Prefix the file with a UTF-8 byte order mark.
+    pub write_bom: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            crlf: false,
+            write_bom: false,
+        }
+    }
+}
+
+/// Semantic file type returned by `FileHandler::classify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum FileKind {
+    Json,
+    Yaml,
+    Csv,
+    Toml,
+    Gzip,
+    Png,
+    Unknown,
+}
+
+const CLASSIFY_SNIFF_BYTES: u64 = 8 * 1024;
+
+impl FileKind {
+    /// `complete` is set when `head` holds the whole file, so JSON can be
+    /// confirmed by parsing; a truncated head needs a structural hint beyond the
+    /// opening bracket. A TOML/INI `[section]` first line is never JSON, whatever
+    /// the file size, and leaves the decision to the extension.
+    fn from_content(head: &[u8], complete: bool) -> Option<Self> {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            return Some(FileKind::Gzip);
+        }
+        if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(FileKind::Png);
+        }
+
+        let text = head.strip_prefix(b"\xef\xbb\xbf").unwrap_or(head);
+        let start = text.iter().position(|b| !b.is_ascii_whitespace())?;
+        let text = &text[start..];
+        match text[0] {
+            b'[' if is_section_header(text) => None,
+            b'{' | b'[' if !complete => looks_like_json(text).then_some(FileKind::Json),
+            b'{' | b'[' => serde_json::from_slice::<serde::de::IgnoredAny>(text)
+                .is_ok()
+                .then_some(FileKind::Json),
+            _ if text.starts_with(b"---") => Some(FileKind::Yaml),
+            _ => None,
+        }
+    }
+
+    fn from_extension(path: &Path) -> Self {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => FileKind::Json,
+            "yaml" | "yml" => FileKind::Yaml,
+            "csv" => FileKind::Csv,
+            "toml" => FileKind::Toml,
+            "gz" | "gzip" => FileKind::Gzip,
+            "png" => FileKind::Png,
+            _ => FileKind::Unknown,
+        }
+    }
+}
+
+/// Whether the first line of `text` is a `[table]` / `[[array]]` header with a
+/// bare key, as in TOML and INI files.
+fn is_section_header(text: &[u8]) -> bool {
+    let line = text.split(|&b| b == b'\n').next().unwrap_or_default();
+    let line = line.trim_ascii_end();
+    let Some(inner) = line.strip_prefix(b"[").and_then(|l| l.strip_suffix(b"]")) else {
+        return false;
+    };
+    let inner = inner
+        .strip_prefix(b"[")
+        .and_then(|l| l.strip_suffix(b"]"))
+        .unwrap_or(inner)
+        .trim_ascii();
+
+    !inner.is_empty()
+        && inner
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.' | b' '))
+}
+
+/// Whether the byte after an opening `{` or `[` can only continue a JSON value.
+fn looks_like_json(text: &[u8]) -> bool {
+    let Some(&next) = text[1..].iter().find(|b| !b.is_ascii_whitespace()) else {
+        return false;
+    };
+    match text[0] {
+        b'{' => matches!(next, b'"' | b'}'),
+        _ => {
+            matches!(next, b'"' | b'{' | b'[' | b']' | b'-' | b't' | b'f' | b'n')
+                || next.is_ascii_digit()
+        }
+    }
+}
+
+/// Ordering for `list_dir` results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(dead_code)]
+pub enum SortBy {
+    #[default]
+    Name,
+    Size,
+    Mtime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct WalkOptions {
+    /// Maximum depth to descend, where direct children are depth 1; `None` is unlimited.
+    pub max_depth: Option<usize>,
+    /// Descend into symlinked directories and include symlinked files; skipped by default.
+    pub follow_symlinks: bool,
+    /// Include dotfiles (and entries with the hidden attribute on Windows); on by default.
+    pub include_hidden: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            follow_symlinks: false,
+            include_hidden: true,
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn is_hidden(entry: &fs::DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+#[cfg(windows)]
+fn is_hidden(entry: &fs::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    entry.file_name().to_string_lossy().starts_with('.')
+        || entry
+            .metadata()
+            .is_ok_and(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+}
+
+/// Cache key for `checksum_cached`: the file and the algorithm it was hashed with.
+pub type ChecksumKey = (PathBuf, String);
+
+#[derive(Debug, Clone)]
+pub struct CachedChecksum {
+    pub modified: std::time::SystemTime,
+    pub size: u64,
+    pub digest: String,
+}
+
+/// Read cache returned by `FileHandler::with_cache`. An entry is reused only
+/// while the file's mtime and size are unchanged; writes through the handle
+/// drop it immediately.
+#[allow(dead_code)]
+pub struct CachedFileHandler {
+    capacity: usize,
+    state: Mutex<ReadCache>,
+}
+
+#[derive(Default)]
+struct ReadCache {
+    entries: HashMap<PathBuf, CachedRead>,
+    /// Incremented on every lookup; entries remember when they were last used.
+    clock: u64,
+    disk_reads: usize,
+}
+
+struct CachedRead {
+    modified: std::time::SystemTime,
+    size: u64,
+    content: String,
+    last_used: u64,
+}
+
+#[allow(dead_code)]
+impl CachedFileHandler {
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            bail!(FileError::NotFound(path.display().to_string()));
+        }
+        let metadata = fs::metadata(path)?;
+        let (modified, size) = (metadata.modified()?, metadata.len());
+
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let now = state.clock;
+        if let Some(entry) = state.entries.get_mut(path) {
+            if entry.modified == modified && entry.size == size {
+                entry.last_used = now;
+                return Ok(entry.content.clone());
+            }
+        }
+
+        let content = FileHandler::read(path)?;
+        state.disk_reads += 1;
+        if self.capacity == 0 {
+            return Ok(content);
+        }
+        if !state.entries.contains_key(path) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.entries.insert(
+            path.to_path_buf(),
+            CachedRead {
+                modified,
+                size,
+                content: content.clone(),
+                last_used: now,
+            },
+        );
+        Ok(content)
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P, content: &str) -> Result<()> {
+        let path = path.as_ref();
+        self.state.lock().unwrap().entries.remove(path);
+        FileHandler::write(path, content)
+    }
+
+    /// How many `read` calls actually went to disk.
+    pub fn disk_reads(&self) -> usize {
+        self.state.lock().unwrap().disk_reads
     }
 }
 
+/// Identifies a directory independently of the path used to reach it.
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+#[cfg(unix)]
+fn dir_id(path: &Path) -> Result<DirId> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_id(path: &Path) -> Result<DirId> {
+    Ok(fs::canonicalize(path)?)
+}
+
+/// Deep-merges mappings with `overlay` taking precedence; any other value is replaced.
+fn merge_yaml_values(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Removes `//` and `/* */` comments and trailing commas, leaving string literals untouched.
+fn strip_jsonc(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if let Some(next) = skip_comment(&chars, i) {
+            i = next;
+            continue;
+        }
+
+        match chars[i] {
+            '"' => {
+                output.push('"');
+                i += 1;
+                while i < chars.len() {
+                    output.push(chars[i]);
+                    match chars[i] {
+                        '\\' if i + 1 < chars.len() => {
+                            output.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        '"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+            ',' => {
+                let next = next_significant(&chars, i + 1);
+                if !matches!(next, Some('}') | Some(']')) {
+                    output.push(',');
+                }
+                i += 1;
+            }
+            c => {
+                output.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    output
+}
+
+/// Returns the index just past a comment starting at `i`, if there is one.
+fn skip_comment(chars: &[char], i: usize) -> Option<usize> {
+    match (chars.get(i), chars.get(i + 1)) {
+        (Some('/'), Some('/')) => Some(
+            (i..chars.len())
+                .find(|&j| chars[j] == '\n')
+                .unwrap_or(chars.len()),
+        ),
+        (Some('/'), Some('*')) => Some(
+            (i + 2..chars.len())
+                .find(|&j| chars[j] == '*' && chars.get(j + 1) == Some(&'/'))
+                .map_or(chars.len(), |j| j + 2),
+        ),
+        _ => None,
+    }
+}
+
+/// Finds the next character after `start` that is neither whitespace nor part of a comment.
+fn next_significant(chars: &[char], start: usize) -> Option<char> {
+    let mut i = start;
+    while i < chars.len() {
+        if let Some(next) = skip_comment(chars, i) {
+            i = next;
+        } else if chars[i].is_whitespace() {
+            i += 1;
+        } else {
+            return Some(chars[i]);
+        }
+    }
+    None
+}
+
+#[cfg(unix)]
+fn user_name(uid: u32) -> Option<String> {
+    uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().into_owned())
+}
+
+#[cfg(unix)]
+fn group_name(gid: u32) -> Option<String> {
+    uzers::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+fn user_name(_uid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(unix))]
+fn group_name(_gid: u32) -> Option<String> {
+    None
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct FileStats {
@@ -298,6 +2373,12 @@ pub struct FileStats {
     pub is_directory: bool,
     pub is_file: bool,
     pub permissions: String,
+    /// Owning user and group ids, with names resolved from the system databases
+    /// when an entry exists. Always `None` on non-Unix platforms.
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
 }
 
 #[cfg(test)]
@@ -313,7 +2394,84 @@ mod tests {
         FileHandler::write(&file_path, "Hello, World!").unwrap();
         let content = FileHandler::read(&file_path).unwrap();
 
-        assert_eq!(content, "Hello, World!");
+        assert_eq!(content, "Hello, World!");
+    }
+
+    #[test]
+    fn test_expand_path() {
+        let expand = |path: &str| {
+            expand_path_with(path, Some("/home/tester".to_string()), |name| {
+                (name == "REPORTS").then(|| "/srv/reports".to_string())
+            })
+        };
+
+        assert_eq!(
+            expand("$REPORTS/out.json").unwrap(),
+            PathBuf::from("/srv/reports/out.json")
+        );
+        assert_eq!(
+            expand("${REPORTS}-old").unwrap(),
+            PathBuf::from("/srv/reports-old")
+        );
+        assert_eq!(
+            expand("~/out.json").unwrap(),
+            PathBuf::from("/home/tester/out.json")
+        );
+        assert!(expand("$UNDEFINED/x").is_err());
+        assert_eq!(expand("plain/path").unwrap(), PathBuf::from("plain/path"));
+
+        // A `$` that cannot start a variable name is kept literally.
+        for literal in ["price$.json", "cost$1", "a$$", "tail$", "x${}y", "${A-B}"] {
+            assert_eq!(expand(literal).unwrap(), PathBuf::from(literal));
+        }
+    }
+
+    #[test]
+    fn test_open_writer_streams_chunks() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("out/large.log");
+
+        let mut writer = FileHandler::open_writer(&file_path).unwrap();
+        let mut expected = String::new();
+        for i in 0..20_000 {
+            let line = format!("chunk {}\n", i);
+            writer.write_all(line.as_bytes()).unwrap();
+            expected.push_str(&line);
+        }
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(FileHandler::read(&file_path).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_cached_reads() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("config.json");
+        FileHandler::write(&config, "{}").unwrap();
+
+        let cache = FileHandler::with_cache(2);
+        assert_eq!(cache.read(&config).unwrap(), "{}");
+        assert_eq!(cache.read(&config).unwrap(), "{}");
+        assert_eq!(cache.disk_reads(), 1);
+
+        cache.write(&config, "{\"a\": 1}").unwrap();
+        assert_eq!(cache.read(&config).unwrap(), "{\"a\": 1}");
+        assert_eq!(cache.disk_reads(), 2);
+
+        // Filling the cache evicts the least recently used entry
+        let other = dir.path().join("other.txt");
+        let third = dir.path().join("third.txt");
+        FileHandler::write(&other, "o").unwrap();
+        FileHandler::write(&third, "t").unwrap();
+        cache.read(&other).unwrap();
+        cache.read(&config).unwrap();
+        cache.read(&third).unwrap();
+        assert_eq!(cache.disk_reads(), 4);
+        cache.read(&config).unwrap();
+        assert_eq!(cache.disk_reads(), 4);
+        cache.read(&other).unwrap();
+        assert_eq!(cache.disk_reads(), 5);
     }
 
     #[test]
@@ -338,6 +2496,267 @@ mod tests {
         assert_eq!(data, loaded);
     }
 
+    #[test]
+    fn test_read_json_or_default() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+
+        let missing: HashMap<String, u32> = FileHandler::read_json_or(&path).unwrap();
+        assert!(missing.is_empty());
+
+        FileHandler::write(&path, "{\"retries\": 3").unwrap();
+        let broken = FileHandler::read_json_or::<HashMap<String, u32>, _>(&path).unwrap_err();
+        assert!(matches!(
+            broken.downcast_ref::<FileError>(),
+            Some(FileError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_peek_records() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Event {
+            id: u32,
+            kind: String,
+        }
+
+        let dir = TempDir::new().unwrap();
+        let ndjson = dir.path().join("events.ndjson");
+        let lines: Vec<String> = (1..=5)
+            .map(|id| format!("{{\"id\": {}, \"kind\": \"click\"}}", id))
+            .collect();
+        FileHandler::write(&ndjson, &(lines.join("\n") + "\n{broken")).unwrap();
+
+        let first: Vec<Event> = FileHandler::peek_records(&ndjson, 2).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(
+            first[1],
+            Event {
+                id: 2,
+                kind: "click".to_string()
+            }
+        );
+        assert!(FileHandler::peek_records::<Event, _>(&ndjson, 10).is_err());
+
+        let csv = dir.path().join("events.csv");
+        FileHandler::write(&csv, "id,kind\n7,view\n8,click\n").unwrap();
+        let rows: Vec<Event> = FileHandler::peek_records(&csv, 10).unwrap();
+        assert_eq!(rows.iter().map(|e| e.id).collect::<Vec<_>>(), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_read_jsonc() {
+        #[derive(Deserialize, PartialEq, Debug)]
+        struct Config {
+            url: String,
+            retries: Vec<i32>,
+        }
+
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("config.jsonc");
+        let content = r#"{
+            // endpoint to call
+            "url": "https://example.com/*path*", /* inline */
+            "retries": [1, 2, 3,],
+        }"#;
+        FileHandler::write(&file_path, content).unwrap();
+
+        let config: Config = FileHandler::read_jsonc(&file_path).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                url: "https://example.com/*path*".to_string(),
+                retries: vec![1, 2, 3],
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_yaml() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join("base.yml");
+        let overlay = dir.path().join("overlay.yml");
+        FileHandler::write(
+            &base,
+            "name: app\ndatabase:\n  host: localhost\n  port: 5432\nhosts: [a, b]\n",
+        )
+        .unwrap();
+        FileHandler::write(&overlay, "database:\n  host: db.prod\nhosts: [c]\n").unwrap();
+
+        let merged = FileHandler::merge_yaml(&base, &overlay).unwrap();
+        let expected: serde_yaml::Value = serde_yaml::from_str(
+            "name: app\ndatabase:\n  host: db.prod\n  port: 5432\nhosts: [c]\n",
+        )
+        .unwrap();
+        assert_eq!(merged, expected);
+
+        FileHandler::write(&overlay, "- not\n- a mapping\n").unwrap();
+        assert!(FileHandler::merge_yaml(&base, &overlay).is_err());
+    }
+
+    #[test]
+    fn test_read_config_layered() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Config {
+            name: String,
+            port: u16,
+        }
+
+        let dir = TempDir::new().unwrap();
+        let base = dir.path().join("config.yml");
+        FileHandler::write(&base, "name: app\nport: 8080\n").unwrap();
+        FileHandler::write(dir.path().join("config.test.yml"), "port: 9090\n").unwrap();
+
+        let config: Config = FileHandler::read_config_layered(&base, "test").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                name: "app".to_string(),
+                port: 9090
+            }
+        );
+
+        let config: Config = FileHandler::read_config_layered(&base, "production").unwrap();
+        assert_eq!(config.port, 8080);
+    }
+
+    #[test]
+    fn test_ini_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("settings.ini");
+        FileHandler::write(
+            &file_path,
+            "verbose = true\n\n[database]\nhost = localhost\nport = 5432\n\n[cache]\nttl = 60\n",
+        )
+        .unwrap();
+
+        let data = FileHandler::read_ini(&file_path).unwrap();
+        assert_eq!(data[""]["verbose"], "true");
+        assert_eq!(data["database"]["host"], "localhost");
+        assert_eq!(data["cache"]["ttl"], "60");
+
+        let copy_path = dir.path().join("copy.ini");
+        FileHandler::write_ini(&copy_path, &data).unwrap();
+        assert_eq!(FileHandler::read_ini(&copy_path).unwrap(), data);
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("blob.b64");
+        let data: Vec<u8> = (0..=255).collect();
+
+        FileHandler::write_base64(&file_path, &data).unwrap();
+        assert_eq!(FileHandler::read_base64(&file_path).unwrap(), data);
+
+        FileHandler::write(&file_path, "not base64!").unwrap();
+        assert!(FileHandler::read_base64(&file_path).is_err());
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("data.gz");
+        let payload = b"compress me ".repeat(100);
+
+        FileHandler::write_gzip(&file_path, &payload).unwrap();
+        assert_eq!(FileHandler::read_gzip(&file_path).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_zstd_round_trip_and_levels() {
+        let dir = TempDir::new().unwrap();
+        let low = dir.path().join("low.zst");
+        let high = dir.path().join("high.zst");
+        let payload: Vec<u8> = (0..20_000).map(|i| (i % 251) as u8).collect();
+
+        FileHandler::write_zstd(&low, &payload, 1).unwrap();
+        FileHandler::write_zstd(&high, &payload, 19).unwrap();
+
+        assert_eq!(FileHandler::read_zstd(&low).unwrap(), payload);
+        assert_eq!(FileHandler::read_zstd(&high).unwrap(), payload);
+        assert!(FileHandler::size(&high).unwrap() <= FileHandler::size(&low).unwrap());
+        assert!(FileHandler::read_zstd(dir.path().join("missing.zst")).is_err());
+    }
+
+    #[test]
+    fn test_auto_compression_dispatch() {
+        let dir = TempDir::new().unwrap();
+        let payload = b"auto detected payload".to_vec();
+
+        for name in ["data.gz", "data.zst", "data.bz2"] {
+            let file_path = dir.path().join(name);
+            FileHandler::compress_auto(&file_path, &payload).unwrap();
+            assert_eq!(FileHandler::decompress_auto(&file_path).unwrap(), payload);
+        }
+
+        // Magic bytes win over a misleading extension
+        FileHandler::write_gzip(dir.path().join("gzip.bin"), &payload).unwrap();
+        assert_eq!(
+            FileHandler::decompress_auto(dir.path().join("gzip.bin")).unwrap(),
+            payload
+        );
+
+        let unknown = dir.path().join("data.rar");
+        let err = FileHandler::compress_auto(&unknown, &payload).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FileError>(),
+            Some(FileError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_classify_prefers_content() {
+        let dir = TempDir::new().unwrap();
+        let disguised = dir.path().join("payload.txt");
+        FileHandler::write(&disguised, "  {\"a\": [1, 2]}\n").unwrap();
+        assert_eq!(FileHandler::classify(&disguised).unwrap(), FileKind::Json);
+
+        let gzip = dir.path().join("archive.json");
+        FileHandler::write_gzip(&gzip, b"{}").unwrap();
+        assert_eq!(FileHandler::classify(&gzip).unwrap(), FileKind::Gzip);
+
+        // Not valid JSON despite the brace, so the extension decides
+        let table = dir.path().join("Cargo.toml");
+        FileHandler::write(&table, "[package]\nname = \"demo\"\n").unwrap();
+        assert_eq!(FileHandler::classify(&table).unwrap(), FileKind::Toml);
+
+        // Past the sniff window the section header still wins over the bracket
+        let padding = "# filler\n".repeat(CLASSIFY_SNIFF_BYTES as usize / 8);
+        FileHandler::write(&table, &format!("[package]\nname = \"demo\"\n{}", padding)).unwrap();
+        assert_eq!(FileHandler::classify(&table).unwrap(), FileKind::Toml);
+
+        let large = dir.path().join("large.txt");
+        let items = vec!["item"; CLASSIFY_SNIFF_BYTES as usize / 4];
+        FileHandler::write(&large, &serde_json::to_string(&items).unwrap()).unwrap();
+        assert_eq!(FileHandler::classify(&large).unwrap(), FileKind::Json);
+
+        let notes = dir.path().join("notes.md");
+        FileHandler::write(&notes, "# Notes\n").unwrap();
+        assert_eq!(FileHandler::classify(&notes).unwrap(), FileKind::Unknown);
+    }
+
+    #[test]
+    fn test_write_csv_excel_options() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("excel.csv");
+        let rows = vec![HashMap::from([("name".to_string(), "Alice".to_string())])];
+
+        let options = CsvOptions {
+            crlf: true,
+            write_bom: true,
+            ..Default::default()
+        };
+        FileHandler::write_csv_with(&file_path, &rows, &options).unwrap();
+
+        let bytes = fs::read(&file_path).unwrap();
+        assert!(bytes.starts_with(UTF8_BOM));
+        assert_eq!(&bytes[UTF8_BOM.len()..], b"name\r\nAlice\r\n");
+
+        FileHandler::write_csv(&file_path, &rows).unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"name\nAlice\n");
+    }
+
     #[test]
     fn test_copy_file() {
         let dir = TempDir::new().unwrap();
@@ -351,6 +2770,65 @@ mod tests {
         assert_eq!(FileHandler::read(&dest).unwrap(), "test content");
     }
 
+    #[test]
+    fn test_copy_preserving_keeps_mtime() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("photo.jpg");
+        let dest = dir.path().join("backup/photo.jpg");
+        FileHandler::write(&source, "pixels").unwrap();
+        let mtime = FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(&source, mtime).unwrap();
+
+        FileHandler::copy_preserving(&source, &dest).unwrap();
+
+        let copied = fs::metadata(&dest).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&copied), mtime);
+        assert_eq!(FileHandler::read(&dest).unwrap(), "pixels");
+    }
+
+    #[test]
+    fn test_custom_buffer_size_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("dest.bin");
+        let data: String = (0..10_000)
+            .map(|i| char::from(b'a' + (i % 26) as u8))
+            .collect();
+        FileHandler::write(&source, &data).unwrap();
+
+        let mut chunks = 0;
+        FileHandler::copy_with_progress_buffered(&source, &dest, 4096, |_| chunks += 1).unwrap();
+        assert_eq!(chunks, 3);
+        assert_eq!(FileHandler::read(&dest).unwrap(), data);
+
+        let expected = FileHandler::checksum_bytes(data.as_bytes(), "sha256").unwrap();
+        for buffer_size in [1, 7, 1 << 20] {
+            let file = File::open(&dest).unwrap();
+            let digest = FileHandler::checksum_reader_buffered(file, "sha256", buffer_size);
+            assert_eq!(digest.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_copy_with_progress() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source.bin");
+        let dest = dir.path().join("nested/dest.bin");
+        FileHandler::write(&source, &"x".repeat(COPY_BUFFER_SIZE * 3 + 10)).unwrap();
+
+        let mut updates = Vec::new();
+        let copied =
+            FileHandler::copy_with_progress(&source, &dest, |p| updates.push(p.clone())).unwrap();
+
+        let last = updates.last().unwrap();
+        assert_eq!(updates.len(), 4);
+        assert_eq!(copied, last.total);
+        assert_eq!(last.bytes, last.total);
+        assert!(last.rate_bytes_s.is_finite());
+        assert_eq!(last.eta, Duration::ZERO);
+        assert_eq!(FileHandler::size(&dest).unwrap(), copied);
+    }
+
     #[test]
     fn test_move_file() {
         let dir = TempDir::new().unwrap();
@@ -365,6 +2843,117 @@ mod tests {
         assert_eq!(FileHandler::read(&dest).unwrap(), "move me");
     }
 
+    #[test]
+    fn test_rename_batch() {
+        let dir = TempDir::new().unwrap();
+        for name in ["img_001.jpg", "img_002.jpg", "notes.txt"] {
+            FileHandler::write(dir.path().join(name), name).unwrap();
+        }
+
+        let pattern = Regex::new(r"^img_(\d+)\.jpg$").unwrap();
+        let renames = FileHandler::rename_batch(dir.path(), &pattern, "photo_$1.jpg").unwrap();
+
+        assert_eq!(
+            renames,
+            vec![
+                (
+                    dir.path().join("img_001.jpg"),
+                    dir.path().join("photo_001.jpg")
+                ),
+                (
+                    dir.path().join("img_002.jpg"),
+                    dir.path().join("photo_002.jpg")
+                ),
+            ]
+        );
+        assert_eq!(
+            FileHandler::read(dir.path().join("photo_001.jpg")).unwrap(),
+            "img_001.jpg"
+        );
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_rename_batch_rolls_back_on_failure() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.txt"), "a").unwrap();
+        FileHandler::write(dir.path().join("c.txt"), "c").unwrap();
+        fs::create_dir(dir.path().join("a_dir")).unwrap();
+
+        // a.txt can move into a_dir, but c.txt has no c_dir to move into
+        let pattern = Regex::new(r"^(\w)\.txt$").unwrap();
+        assert!(FileHandler::rename_batch(dir.path(), &pattern, "${1}_dir/$1.log").is_err());
+
+        assert_eq!(FileHandler::read(dir.path().join("a.txt")).unwrap(), "a");
+        assert_eq!(FileHandler::read(dir.path().join("c.txt")).unwrap(), "c");
+        assert!(!dir.path().join("a_dir/a.log").exists());
+    }
+
+    #[test]
+    fn test_rename_batch_collision() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a_1.log"), "a").unwrap();
+        FileHandler::write(dir.path().join("b_1.log"), "b").unwrap();
+
+        let pattern = Regex::new(r"^\w_(\d+)\.log$").unwrap();
+        assert!(FileHandler::rename_batch(dir.path(), &pattern, "log_$1.log").is_err());
+        assert!(dir.path().join("a_1.log").exists());
+        assert!(dir.path().join("b_1.log").exists());
+
+        // A target that is also a source is a chain, not a collision
+        let chain_dir = TempDir::new().unwrap();
+        FileHandler::write(chain_dir.path().join("x"), "first").unwrap();
+        FileHandler::write(chain_dir.path().join("x.bak"), "second").unwrap();
+        let everything = Regex::new(r"^(.*)$").unwrap();
+        FileHandler::rename_batch(chain_dir.path(), &everything, "$1.bak").unwrap();
+        assert_eq!(
+            FileHandler::read(chain_dir.path().join("x.bak")).unwrap(),
+            "first"
+        );
+        assert_eq!(
+            FileHandler::read(chain_dir.path().join("x.bak.bak")).unwrap(),
+            "second"
+        );
+    }
+
+    #[test]
+    fn test_part_number_width_grows_with_part_count() {
+        assert_eq!(part_number_width(0), 4);
+        assert_eq!(part_number_width(9_999), 4);
+        assert_eq!(part_number_width(10_000), 5);
+        assert_eq!(part_number_width(1_234_567), 7);
+    }
+
+    #[test]
+    fn test_split_into_output_dir() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("big.bin");
+        FileHandler::write(&source, "abcdefghij").unwrap();
+
+        let output_dir = dir.path().join("parts");
+        let parts = FileHandler::split(&source, 4, Some(&output_dir)).unwrap();
+
+        let names: Vec<_> = parts
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "big.bin.part_0001",
+                "big.bin.part_0002",
+                "big.bin.part_0003"
+            ]
+        );
+        assert!(parts
+            .iter()
+            .all(|p| p.parent() == Some(output_dir.as_path())));
+        assert_eq!(FileHandler::read(&parts[2]).unwrap(), "ij");
+
+        let beside = FileHandler::split(&source, 10, None).unwrap();
+        assert_eq!(beside, vec![dir.path().join("big.bin.part_0001")]);
+    }
+
     #[test]
     fn test_delete_file() {
         let dir = TempDir::new().unwrap();
@@ -377,6 +2966,535 @@ mod tests {
         assert!(!file_path.exists());
     }
 
+    #[test]
+    fn test_cross_device_rename_falls_back_to_copy() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source.txt");
+        let destination = dir.path().join("destination.txt");
+        FileHandler::write(&source, "payload").unwrap();
+
+        let exdev = |_: &Path, _: &Path| Err(std::io::ErrorKind::CrossesDevices.into());
+        rename_with(&source, &destination, exdev).unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(FileHandler::read(&destination).unwrap(), "payload");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let denied = |_: &Path, _: &Path| Err(std::io::ErrorKind::PermissionDenied.into());
+        assert!(rename_with(&destination, &source, denied).is_err());
+        assert!(destination.exists());
+    }
+
+    #[test]
+    fn test_atomic_write_in_custom_temp_dir() {
+        let dir = TempDir::new().unwrap();
+        let staging = dir.path().join("staging");
+        fs::create_dir(&staging).unwrap();
+        let target = dir.path().join("out.txt");
+
+        FileHandler::atomic_write_in(&target, "first", Some(&staging)).unwrap();
+        FileHandler::atomic_write_in(&target, "second", Some(&staging)).unwrap();
+
+        assert_eq!(FileHandler::read(&target).unwrap(), "second");
+        assert_eq!(fs::read_dir(&staging).unwrap().count(), 0);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_temp_file_removed_on_drop() {
+        let temp = FileHandler::temp_file("report-", ".json").unwrap();
+        let path = temp.to_path_buf();
+        assert!(path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("report-"));
+        assert_eq!(path.extension().unwrap(), "json");
+
+        FileHandler::write_json(&temp, &serde_json::json!({"ok": true}), false).unwrap();
+        assert!(FileHandler::exists(&path));
+
+        drop(temp);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_truncate() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("app.log");
+        FileHandler::write(&file_path, &"x".repeat(100)).unwrap();
+
+        FileHandler::truncate(&file_path, 10).unwrap();
+        assert_eq!(FileHandler::size(&file_path).unwrap(), 10);
+
+        FileHandler::truncate(&file_path, 12).unwrap();
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"xxxxxxxxxx\0\0");
+
+        let err = FileHandler::truncate(dir.path().join("missing.log"), 0).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FileError>(),
+            Some(FileError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_prepend() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("data.csv");
+        FileHandler::write(&file_path, "1,2\n3,4\n").unwrap();
+
+        FileHandler::prepend(&file_path, "a,b\n").unwrap();
+
+        assert_eq!(FileHandler::read(&file_path).unwrap(), "a,b\n1,2\n3,4\n");
+        assert_eq!(
+            FileHandler::walk_dir(dir.path(), &WalkOptions::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prepend_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("deploy.sh");
+        FileHandler::write(&file_path, "echo deploy\n").unwrap();
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o750)).unwrap();
+
+        FileHandler::prepend(&file_path, "#!/bin/sh\n").unwrap();
+
+        let mode = fs::metadata(&file_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750);
+        assert_eq!(
+            FileHandler::read(&file_path).unwrap(),
+            "#!/bin/sh\necho deploy\n"
+        );
+    }
+
+    #[test]
+    fn test_grep() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("app.log");
+        FileHandler::write(&file_path, "ok start\r\nERROR 500 db\nok\nERROR 404 x").unwrap();
+
+        let options = GrepOptions::default();
+        let matches =
+            FileHandler::grep(&file_path, &Regex::new(r"ERROR \d+").unwrap(), &options).unwrap();
+        assert_eq!(
+            matches,
+            vec![
+                GrepMatch {
+                    line_number: 2,
+                    line: "ERROR 500 db".to_string()
+                },
+                GrepMatch {
+                    line_number: 4,
+                    line: "ERROR 404 x".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            FileHandler::grep_count(&file_path, &Regex::new("ERROR").unwrap(), &options).unwrap(),
+            2
+        );
+        let inverted = GrepOptions {
+            invert_match: true,
+            ..Default::default()
+        };
+        let kept: Vec<String> =
+            FileHandler::grep(&file_path, &Regex::new("ERROR").unwrap(), &inverted)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.line)
+                .collect();
+        assert_eq!(kept, vec!["ok start", "ok"]);
+
+        assert!(FileHandler::grep(
+            dir.path().join("missing.log"),
+            &Regex::new("x").unwrap(),
+            &options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_grep_context_groups() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("app.log");
+        let lines: Vec<String> = (1..=12).map(|i| format!("line {}", i)).collect();
+        let mut content = lines.join("\n");
+        content = content.replace("line 3\n", "line 3 ERROR\n");
+        content = content.replace("line 5\n", "line 5 ERROR\n");
+        content = content.replace("line 10\n", "line 10 ERROR\n");
+        FileHandler::write(&file_path, &content).unwrap();
+
+        let pattern = Regex::new("ERROR").unwrap();
+        let groups =
+            FileHandler::grep_context(&file_path, &pattern, &GrepOptions::default(), 1, 1).unwrap();
+        let numbers: Vec<Vec<usize>> = groups
+            .iter()
+            .map(|g| g.lines.iter().map(|l| l.line_number).collect())
+            .collect();
+        assert_eq!(numbers, vec![vec![2, 3, 4, 5, 6], vec![9, 10, 11]]);
+        assert!(groups[1].lines[1].is_match && !groups[1].lines[0].is_match);
+    }
+
+    #[test]
+    fn test_max_line_length() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("input.csv");
+        FileHandler::write(
+            &file_path,
+            &format!("id,note\r\n1,short\n2,{}\n", "x".repeat(100)),
+        )
+        .unwrap();
+
+        let limited = LineOptions {
+            max_line_length: Some(16),
+        };
+        let err = FileHandler::read_lines(&file_path, &limited).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FileError>(),
+            Some(FileError::OperationFailed(msg)) if msg.contains("line 3")
+        ));
+        assert!(FileHandler::for_each_csv_record(&file_path, &limited, |_| Ok(())).is_err());
+        let grep_limited = GrepOptions {
+            lines: limited.clone(),
+            ..Default::default()
+        };
+        assert!(
+            FileHandler::grep(&file_path, &Regex::new("short").unwrap(), &grep_limited).is_err()
+        );
+
+        let unlimited = LineOptions::default();
+        assert_eq!(
+            FileHandler::read_lines(&file_path, &unlimited)
+                .unwrap()
+                .len(),
+            3
+        );
+        let mut notes = Vec::new();
+        let count = FileHandler::for_each_csv_record(&file_path, &unlimited, |record| {
+            notes.push(record["note"].clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(notes[0], "short");
+    }
+
+    #[test]
+    fn test_diff() {
+        let dir = TempDir::new().unwrap();
+        let old = dir.path().join("old.txt");
+        let new = dir.path().join("new.txt");
+        FileHandler::write(&old, "a\nb\nc\n").unwrap();
+        FileHandler::write(&new, "a\nB\nc\nd\n").unwrap();
+
+        assert_eq!(
+            FileHandler::diff(&old, &new).unwrap(),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("B".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checksum_prefix() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("movie.bin");
+        FileHandler::write(&file_path, "HEADpayload").unwrap();
+
+        assert_eq!(
+            FileHandler::checksum_prefix(&file_path, "sha256", 4).unwrap(),
+            FileHandler::checksum_bytes(b"HEAD", "sha256").unwrap()
+        );
+        assert_eq!(
+            FileHandler::checksum_prefix(&file_path, "md5", 1 << 20).unwrap(),
+            FileHandler::checksum(&file_path, "md5").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_purge_older_than() {
+        let dir = TempDir::new().unwrap();
+        let old = dir.path().join("old.log");
+        let new = dir.path().join("new.log");
+        let nested_old = dir.path().join("archive/older.log");
+        for path in [&old, &new, &nested_old] {
+            FileHandler::write(path, "entry").unwrap();
+        }
+        let two_days_ago = FileTime::from_system_time(
+            std::time::SystemTime::now() - Duration::from_secs(2 * 24 * 3600),
+        );
+        filetime::set_file_mtime(&old, two_days_ago).unwrap();
+        filetime::set_file_mtime(&nested_old, two_days_ago).unwrap();
+
+        let day = Duration::from_secs(24 * 3600);
+        assert_eq!(
+            FileHandler::purge_older_than(dir.path(), day, false).unwrap(),
+            vec![old.clone()]
+        );
+        assert!(!old.exists());
+        assert!(new.exists());
+        assert!(nested_old.exists());
+
+        assert_eq!(
+            FileHandler::purge_older_than(dir.path(), day, true).unwrap(),
+            vec![nested_old.clone()]
+        );
+        assert!(dir.path().join("archive").is_dir());
+    }
+
+    #[test]
+    fn test_retain_newest() {
+        let dir = TempDir::new().unwrap();
+        let backups: Vec<PathBuf> = (1..=5)
+            .map(|day| dir.path().join(format!("backup-0{}.tar", day)))
+            .collect();
+        for (day, path) in backups.iter().enumerate() {
+            FileHandler::write(path, "archive").unwrap();
+            filetime::set_file_mtime(
+                path,
+                FileTime::from_unix_time(1_700_000_000 + day as i64 * 86_400, 0),
+            )
+            .unwrap();
+        }
+        let unrelated = dir.path().join("notes.txt");
+        FileHandler::write(&unrelated, "keep me").unwrap();
+        filetime::set_file_mtime(&unrelated, FileTime::from_unix_time(0, 0)).unwrap();
+
+        let pattern = Regex::new(r"^backup-\d+\.tar$").unwrap();
+        let mut deleted = FileHandler::retain_newest(dir.path(), &pattern, 2).unwrap();
+        deleted.sort();
+
+        assert_eq!(deleted, backups[..3].to_vec());
+        assert!(backups[3].exists() && backups[4].exists());
+        assert!(unrelated.exists());
+        assert!(FileHandler::retain_newest(dir.path(), &pattern, 5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_include_hidden() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join(".secret"), "token").unwrap();
+        FileHandler::write(dir.path().join(".config/app.yml"), "a: 1").unwrap();
+        FileHandler::write(dir.path().join("visible.txt"), "hi").unwrap();
+
+        let all = FileHandler::list_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            SortBy::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(all.len(), 3);
+
+        let options = WalkOptions {
+            include_hidden: false,
+            ..Default::default()
+        };
+        let listed = FileHandler::list_dir(dir.path(), &options, SortBy::Name, false).unwrap();
+        assert_eq!(listed, vec![dir.path().join("visible.txt")]);
+        let walked = FileHandler::walk_dir(dir.path(), &options).unwrap();
+        assert_eq!(walked, vec![dir.path().join("visible.txt")]);
+    }
+
+    #[test]
+    fn test_list_dir_sorted_by_size() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("a.txt"), "x").unwrap();
+        FileHandler::write(dir.path().join("b.txt"), "xxxxxxxxxx").unwrap();
+        FileHandler::write(dir.path().join("c.txt"), "xxxxx").unwrap();
+        FileHandler::write(dir.path().join("d.txt"), "xxxxx").unwrap();
+
+        let options = WalkOptions::default();
+        let names = |sort, descending| -> Vec<String> {
+            FileHandler::list_dir(dir.path(), &options, sort, descending)
+                .unwrap()
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            names(SortBy::Size, true),
+            vec!["b.txt", "c.txt", "d.txt", "a.txt"]
+        );
+        assert_eq!(
+            names(SortBy::Size, false),
+            vec!["a.txt", "c.txt", "d.txt", "b.txt"]
+        );
+        assert_eq!(
+            names(SortBy::Name, false),
+            vec!["a.txt", "b.txt", "c.txt", "d.txt"]
+        );
+    }
+
+    #[test]
+    fn test_walk_dir_max_depth() {
+        let dir = TempDir::new().unwrap();
+        FileHandler::write(dir.path().join("top.txt"), "1").unwrap();
+        FileHandler::write(dir.path().join("a/mid.txt"), "2").unwrap();
+        FileHandler::write(dir.path().join("a/b/deep.txt"), "3").unwrap();
+
+        let all = FileHandler::walk_dir(dir.path(), &WalkOptions::default()).unwrap();
+        assert_eq!(all.len(), 5);
+
+        let options = WalkOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let top = FileHandler::walk_dir(dir.path(), &options).unwrap();
+        assert_eq!(top, vec![dir.path().join("a"), dir.path().join("top.txt")]);
+
+        let found = FileHandler::find(dir.path(), "*.txt", &options).unwrap();
+        assert_eq!(found, vec![dir.path().join("top.txt")]);
+    }
+
+    #[test]
+    fn test_walk_dir_with_progress() {
+        let dir = TempDir::new().unwrap();
+        for name in ["a.txt", "sub/b.txt", "sub/deeper/c.txt", "z.txt"] {
+            FileHandler::write(dir.path().join(name), "x").unwrap();
+        }
+        let options = WalkOptions::default();
+
+        let mut seen = 0;
+        let total = FileHandler::walk_dir_with_progress(dir.path(), &options, |_| {
+            seen += 1;
+            true
+        })
+        .unwrap();
+        let entries = FileHandler::walk_dir(dir.path(), &options).unwrap();
+        assert_eq!(seen, entries.len());
+        assert_eq!(total, entries.len());
+
+        let stopped = FileHandler::walk_dir_with_progress(dir.path(), &options, |entry| {
+            !entry.ends_with("b.txt")
+        })
+        .unwrap();
+        assert_eq!(stopped, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stats_owner_is_current_user() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("owned.txt");
+        FileHandler::write(&file_path, "mine").unwrap();
+
+        let stats = FileHandler::stats(&file_path).unwrap();
+        assert_eq!(stats.uid, Some(uzers::get_current_uid()));
+        assert_eq!(stats.gid, Some(uzers::get_effective_gid()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_dir_follow_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new().unwrap();
+        let tree = dir.path().join("tree");
+        let outside = dir.path().join("outside");
+        FileHandler::write(tree.join("a.txt"), "aaaa").unwrap();
+        FileHandler::write(outside.join("b.txt"), "bb").unwrap();
+        symlink(&outside, tree.join("link")).unwrap();
+        // Points back at the walk root, forming a cycle when links are followed.
+        symlink(&tree, outside.join("back")).unwrap();
+
+        let skipped = FileHandler::walk_dir(&tree, &WalkOptions::default()).unwrap();
+        assert_eq!(skipped, vec![tree.join("a.txt")]);
+        assert_eq!(
+            FileHandler::dir_size(&tree, &WalkOptions::default()).unwrap(),
+            4
+        );
+
+        let options = WalkOptions {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+        let followed = FileHandler::walk_dir(&tree, &options).unwrap();
+        assert_eq!(
+            followed,
+            vec![
+                tree.join("a.txt"),
+                tree.join("link"),
+                tree.join("link/b.txt"),
+                tree.join("link/back"),
+            ]
+        );
+        assert_eq!(FileHandler::dir_size(&tree, &options).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_tree_hash_tracks_content_not_order() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let files = [
+            ("a.txt", "alpha"),
+            ("nested/b.txt", "beta"),
+            ("z.bin", "zeta"),
+        ];
+        for (name, content) in files {
+            FileHandler::write(first.path().join(name), content).unwrap();
+        }
+        for (name, content) in files.iter().rev() {
+            FileHandler::write(second.path().join(name), content).unwrap();
+        }
+
+        let root = FileHandler::tree_hash(first.path(), "sha256").unwrap();
+        assert_eq!(root.len(), 64);
+        assert_eq!(
+            FileHandler::tree_hash(second.path(), "sha256").unwrap(),
+            root
+        );
+
+        FileHandler::write(second.path().join("nested/b.txt"), "beta!").unwrap();
+        assert_ne!(
+            FileHandler::tree_hash(second.path(), "sha256").unwrap(),
+            root
+        );
+    }
+
+    #[test]
+    fn test_cas_store_and_get() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store");
+        let artifact = dir.path().join("build.tar");
+        FileHandler::write(&artifact, "artifact bytes").unwrap();
+
+        let hash = FileHandler::cas_store(&artifact, &store, "sha256").unwrap();
+        let stored = FileHandler::cas_get(&store, &hash).unwrap();
+        assert_eq!(stored, store.join(&hash[..2]).join(&hash[2..]));
+        assert_eq!(FileHandler::read(&stored).unwrap(), "artifact bytes");
+
+        let copy = dir.path().join("copy.tar");
+        FileHandler::write(&copy, "artifact bytes").unwrap();
+        assert_eq!(
+            FileHandler::cas_store(&copy, &store, "sha256").unwrap(),
+            hash
+        );
+        assert_eq!(
+            FileHandler::walk_dir(&store, &WalkOptions::default())
+                .unwrap()
+                .len(),
+            2
+        );
+
+        assert!(FileHandler::cas_get(&store, &"0".repeat(64)).is_err());
+        assert!(FileHandler::cas_get(&store, "../etc").is_err());
+    }
+
     #[test]
     fn test_checksum() {
         let dir = TempDir::new().unwrap();
@@ -389,5 +3507,137 @@ mod tests {
 
         let md5 = FileHandler::checksum(&file_path, "md5").unwrap();
         assert_eq!(md5.len(), 32); // MD5 is 32 hex chars
+
+        assert!(
+            FileHandler::verify_checksum(&file_path, "sha256", &sha256.to_uppercase()).unwrap()
+        );
+        assert!(!FileHandler::verify_checksum(&file_path, "md5", &sha256).unwrap());
+    }
+
+    #[test]
+    fn test_checksum_cached_skips_unchanged_files() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("big.bin");
+        FileHandler::write(&file_path, "first").unwrap();
+
+        let mut cache = HashMap::new();
+        let first = FileHandler::checksum_cached(&file_path, "sha256", &mut cache).unwrap();
+        let modified = fs::metadata(&file_path).unwrap().modified().unwrap();
+
+        // Same size and mtime: the stale cached digest comes back without re-reading.
+        FileHandler::write(&file_path, "other").unwrap();
+        File::options()
+            .write(true)
+            .open(&file_path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        let second = FileHandler::checksum_cached(&file_path, "sha256", &mut cache).unwrap();
+        assert_eq!(first, second);
+
+        FileHandler::write(&file_path, "changed content").unwrap();
+        let third = FileHandler::checksum_cached(&file_path, "sha256", &mut cache).unwrap();
+        assert_ne!(first, third);
+        assert_eq!(third, FileHandler::checksum(&file_path, "sha256").unwrap());
+
+        let md5 = FileHandler::checksum_cached(&file_path, "md5", &mut cache).unwrap();
+        assert_eq!(md5, FileHandler::checksum(&file_path, "md5").unwrap());
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("mixed.txt");
+        FileHandler::write(&file_path, "one\r\ntwo\r\nthree\nfour").unwrap();
+
+        let changed = FileHandler::normalize_line_endings(&file_path, LineEnding::Lf).unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            FileHandler::read(&file_path).unwrap(),
+            "one\ntwo\nthree\nfour"
+        );
+
+        let changed = FileHandler::normalize_line_endings(&file_path, LineEnding::Crlf).unwrap();
+        assert_eq!(changed, 3);
+        assert_eq!(
+            FileHandler::read(&file_path).unwrap(),
+            "one\r\ntwo\r\nthree\r\nfour"
+        );
+        assert_eq!(
+            FileHandler::normalize_line_endings(&file_path, LineEnding::Crlf).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_fix_whitespace() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("messy.txt");
+        FileHandler::write(&file_path, "keep  \r\ntabs\t\nlast line ").unwrap();
+
+        assert!(FileHandler::fix_whitespace(&file_path, true, true).unwrap());
+        assert_eq!(
+            FileHandler::read(&file_path).unwrap(),
+            "keep\r\ntabs\nlast line\n"
+        );
+
+        let before = std::fs::read(&file_path).unwrap();
+        assert!(!FileHandler::fix_whitespace(&file_path, true, true).unwrap());
+        assert_eq!(std::fs::read(&file_path).unwrap(), before);
+
+        FileHandler::write(&file_path, "no newline  ").unwrap();
+        assert!(FileHandler::fix_whitespace(&file_path, false, true).unwrap());
+        assert_eq!(FileHandler::read(&file_path).unwrap(), "no newline  \n");
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("input.yml");
+        let derived = dir.path().join("input.json");
+        FileHandler::write(&source, "a: 1").unwrap();
+
+        assert!(FileHandler::is_stale(&source, &derived).unwrap());
+
+        FileHandler::write(&derived, r#"{"a": 1}"#).unwrap();
+        let created = std::time::SystemTime::now() - Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(created)
+            .unwrap();
+        assert!(!FileHandler::is_stale(&source, &derived).unwrap());
+
+        File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert!(FileHandler::is_stale(&source, &derived).unwrap());
+
+        assert!(FileHandler::is_stale(dir.path().join("missing"), &derived).is_err());
+    }
+
+    #[test]
+    fn test_checksum_reader_matches_file_digest() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("large.bin");
+        let data: Vec<u8> = (0..COPY_BUFFER_SIZE * 3 + 17)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        FileHandler::write_bytes(&file_path, &data).unwrap();
+
+        for algorithm in ["md5", "sha1", "sha256", "sha512"] {
+            assert_eq!(
+                FileHandler::checksum_reader(std::io::Cursor::new(&data), algorithm).unwrap(),
+                FileHandler::checksum(&file_path, algorithm).unwrap()
+            );
+        }
+        assert_eq!(
+            FileHandler::checksum_bytes(b"abc", "sha256").unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
     }
 }
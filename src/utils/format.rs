@@ -0,0 +1,208 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use num_format::{Locale, ToFormattedString};
+use std::fmt::Write;
+
+use crate::utils::file_handler::DiffLine;
+
+const BYTES_PER_LINE: usize = 16;
+
+#[allow(dead_code)]
+pub fn hexdump(bytes: &[u8]) -> String {
+    hexdump_at(bytes, 0)
+}
+
+/// Renders `bytes` in the `hexdump -C` layout, numbering lines from `base_offset`.
+pub fn hexdump_at(bytes: &[u8], base_offset: u64) -> String {
+    let mut output = String::new();
+
+    for (index, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let offset = base_offset + (index * BYTES_PER_LINE) as u64;
+        let _ = write!(output, "{:08x} ", offset);
+
+        for column in 0..BYTES_PER_LINE {
+            if column % 8 == 0 {
+                output.push(' ');
+            }
+            match chunk.get(column) {
+                Some(byte) => {
+                    let _ = write!(output, "{:02x} ", byte);
+                }
+                None => output.push_str("   "),
+            }
+        }
+
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(output, " |{}|", ascii);
+    }
+
+    let _ = writeln!(output, "{:08x}", base_offset + bytes.len() as u64);
+    output
+}
+
+/// Looks up a number-formatting locale such as `en`, `de` or `fr`.
+pub fn locale(name: &str) -> Result<Locale> {
+    Locale::from_name(name).map_err(|_| anyhow!("Unknown locale '{}'", name))
+}
+
+/// Formats `value` with `decimals` places using the locale's thousands
+/// separator and decimal mark, e.g. `1.234.567,89` for `de`.
+pub fn grouped(value: f64, decimals: usize, locale: &Locale) -> String {
+    let fixed = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+    let mut output = String::new();
+    if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        output.push_str(locale.minus_sign());
+    }
+    match integer.parse::<u128>() {
+        Ok(integer) => output.push_str(&integer.to_formatted_string(locale)),
+        Err(_) => output.push_str(integer),
+    }
+    if !fraction.is_empty() {
+        output.push_str(locale.decimal());
+        output.push_str(fraction);
+    }
+    output
+}
+
+/// Renders `lines` as unified-diff hunks with `context` unchanged lines around
+/// each change. With `color`, added lines are green, removed red, headers cyan.
+pub fn render_diff(lines: &[DiffLine], context: usize, color: bool) -> String {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let (start, end) = (
+            i.saturating_sub(context),
+            (i + context + 1).min(lines.len()),
+        );
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = String::new();
+    for (start, end) in hunks {
+        let in_old = |line: &DiffLine| !matches!(line, DiffLine::Added(_));
+        let in_new = |line: &DiffLine| !matches!(line, DiffLine::Removed(_));
+        let old_start = lines[..start].iter().filter(|l| in_old(l)).count() + 1;
+        let new_start = lines[..start].iter().filter(|l| in_new(l)).count() + 1;
+        let old_len = lines[start..end].iter().filter(|l| in_old(l)).count();
+        let new_len = lines[start..end].iter().filter(|l| in_new(l)).count();
+
+        // Empty ranges name the line before them, as in `diff -u`.
+        let header = format!(
+            "@@ -{},{} +{},{} @@",
+            if old_len == 0 {
+                old_start - 1
+            } else {
+                old_start
+            },
+            old_len,
+            if new_len == 0 {
+                new_start - 1
+            } else {
+                new_start
+            },
+            new_len
+        );
+        let _ = writeln!(output, "{}", paint(header, color, Color::Cyan));
+
+        for line in &lines[start..end] {
+            let rendered = match line {
+                DiffLine::Same(text) => format!(" {}", text),
+                DiffLine::Added(text) => paint(format!("+{}", text), color, Color::Green),
+                DiffLine::Removed(text) => paint(format!("-{}", text), color, Color::Red),
+            };
+            let _ = writeln!(output, "{}", rendered);
+        }
+    }
+    output
+}
+
+fn paint(text: String, color: bool, with: Color) -> String {
+    if color {
+        text.color(with).to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_short_slice() {
+        let output = hexdump(b"AB\x00\n");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "00000000  41 42 00 0a                                       |AB..|"
+        );
+        assert_eq!(lines[1], "00000004");
+    }
+
+    #[test]
+    fn test_hexdump_full_line_and_offset() {
+        let bytes: Vec<u8> = (0x41..0x51).collect();
+        let output = hexdump_at(&bytes, 0x20);
+
+        assert!(output.starts_with(
+            "00000020  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|"
+        ));
+        assert!(output.ends_with("00000030\n"));
+    }
+
+    #[test]
+    fn test_grouped_by_locale() {
+        assert_eq!(grouped(1234567.891, 2, &Locale::en), "1,234,567.89");
+        assert_eq!(
+            grouped(1234567.891, 2, &locale("de").unwrap()),
+            "1.234.567,89"
+        );
+        assert_eq!(grouped(-1500.0, 0, &Locale::en), "-1,500");
+        assert_eq!(grouped(999.5, 1, &Locale::en), "999.5");
+        assert!(locale("xx-nowhere").is_err());
+    }
+
+    #[test]
+    fn test_render_diff_plain() {
+        let same = |s: &str| DiffLine::Same(s.to_string());
+        let mut lines: Vec<DiffLine> = (1..=10).map(|i| same(&i.to_string())).collect();
+        lines[4] = DiffLine::Removed("5".to_string());
+        lines.insert(5, DiffLine::Added("five".to_string()));
+
+        let output = render_diff(&lines, 1, false);
+        assert_eq!(output, "@@ -4,3 +4,3 @@\n 4\n-5\n+five\n 6\n");
+        assert!(!output.contains('\u{1b}'));
+
+        assert_eq!(render_diff(&lines[..3], 3, false), "");
+        assert_eq!(
+            render_diff(&[DiffLine::Added("x".to_string())], 3, false),
+            "@@ -0,0 +1,1 @@\n+x\n"
+        );
+    }
+
+    #[test]
+    fn test_hexdump_empty() {
+        assert_eq!(hexdump(&[]), "00000000\n");
+    }
+}
@@ -0,0 +1,532 @@
+use anyhow::{bail, Result};
+use serde::de::{self, Deserialize, Deserializer as _, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+
+/// Looks up a node by RFC 6901 JSON pointer, e.g. `/users/0/name`.
+pub fn get<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    value.pointer(pointer)
+}
+
+/// Writes `new` at `pointer`, creating intermediate objects for missing keys.
+/// Fails if the path runs through a scalar or past the end of an array.
+pub fn set(value: &mut Value, pointer: &str, new: Value) -> Result<()> {
+    let tokens = parse_pointer(pointer)?;
+    let Some((last, parents)) = tokens.split_last() else {
+        *value = new;
+        return Ok(());
+    };
+
+    let mut current = value;
+    for (depth, token) in parents.iter().enumerate() {
+        current = match current {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => {
+                let index = array_index(token, items.len(), pointer)?;
+                match items.get_mut(index) {
+                    Some(item) => item,
+                    None => bail!("Cannot set {}: index {} is out of bounds", pointer, token),
+                }
+            }
+            scalar => bail!(
+                "Cannot set {}: '{}' is a {}",
+                pointer,
+                join_pointer(&tokens[..depth]),
+                type_name(scalar)
+            ),
+        };
+    }
+
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), new);
+        }
+        Value::Array(items) => {
+            let index = array_index(last, items.len(), pointer)?;
+            if index < items.len() {
+                items[index] = new;
+            } else if index == items.len() {
+                items.push(new);
+            } else {
+                bail!("Cannot set {}: index {} is out of bounds", pointer, last);
+            }
+        }
+        scalar => bail!(
+            "Cannot set {}: '{}' is a {}",
+            pointer,
+            join_pointer(parents),
+            type_name(scalar)
+        ),
+    }
+
+    Ok(())
+}
+
+/// Removes and returns the node at `pointer`; a path that does not resolve is a no-op.
+pub fn remove(value: &mut Value, pointer: &str) -> Option<Value> {
+    let (parent, token) = pointer.rsplit_once('/')?;
+    let token = token.replace("~1", "/").replace("~0", "~");
+
+    match value.pointer_mut(parent)? {
+        Value::Object(map) => map.remove(&token),
+        Value::Array(items) => {
+            let index: usize = token.parse().ok()?;
+            (index < items.len()).then(|| items.remove(index))
+        }
+        _ => None,
+    }
+}
+
+/// Collapses nested objects and arrays into one level keyed by dotted paths,
+/// e.g. `{"a": {"b": [1]}}` becomes `{"a.b.0": 1}`. Empty containers are kept
+/// as leaves; a scalar root is returned under the empty key.
+pub fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    let mut flat = BTreeMap::new();
+    flatten_into(value, String::new(), &mut flat);
+    flat
+}
+
+fn flatten_into(value: &Value, prefix: String, flat: &mut BTreeMap<String, Value>) {
+    let child_key = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                flatten_into(child, child_key(key), flat);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                flatten_into(child, child_key(&index.to_string()), flat);
+            }
+        }
+        leaf => {
+            flat.insert(prefix, leaf.clone());
+        }
+    }
+}
+
+/// Pretty-prints `value` using `indent` (e.g. four spaces or a tab) per nesting level.
+pub fn to_string_indented(value: &Value, indent: &str) -> Result<String> {
+    let mut output = Vec::new();
+    let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    value.serialize(&mut serializer)?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Serializes `value` as RFC 8785 (JCS) canonical JSON: no whitespace, object
+/// keys sorted by UTF-16 code units and numbers in ECMAScript's shortest form,
+/// so semantically equal documents produce identical bytes.
+pub fn to_canonical_string(value: &Value) -> Result<String> {
+    let mut output = String::new();
+    write_canonical(value, &mut output)?;
+    Ok(output)
+}
+
+fn write_canonical(value: &Value, output: &mut String) -> Result<()> {
+    match value {
+        Value::Null | Value::Bool(_) | Value::String(_) => {
+            output.push_str(&serde_json::to_string(value)?)
+        }
+        Value::Number(number) => match number.as_f64() {
+            Some(number) if number.is_finite() => output.push_str(&canonical_number(number)),
+            _ => bail!("Cannot canonicalize number {}", number),
+        },
+        Value::Array(elements) => {
+            output.push('[');
+            for (i, element) in elements.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_canonical(element, output)?;
+            }
+            output.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_cached_key(|(key, _)| key.encode_utf16().collect::<Vec<u16>>());
+            output.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                output.push_str(&serde_json::to_string(key)?);
+                output.push(':');
+                write_canonical(value, output)?;
+            }
+            output.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Formats a finite double like ECMAScript's `Number.prototype.toString`.
+fn canonical_number(number: f64) -> String {
+    if number == 0.0 {
+        return "0".to_string();
+    }
+
+    // `{:e}` yields the shortest round-tripping digits, e.g. `-1.25e-7`.
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let length = digits.len() as i32;
+    // Position of the decimal point relative to the start of `digits`.
+    let point = exponent + 1;
+
+    let formatted = if length <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - length) as usize))
+    } else if 0 < point && point <= 21 {
+        format!(
+            "{}.{}",
+            &digits[..point as usize],
+            &digits[point as usize..]
+        )
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        let fraction = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{}", rest)
+        };
+        format!("{}{}e{}{}", first, fraction, sign, exponent.abs())
+    };
+
+    if number < 0.0 {
+        format!("-{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+/// Parses a top-level JSON array from `reader` one element at a time, handing
+/// each to `on_element` so only a single element is held in memory. Returns the
+/// number of elements; an error from the callback stops the parse and is returned.
+pub fn for_each_array_element<R, F>(reader: R, mut on_element: F) -> Result<usize>
+where
+    R: Read,
+    F: FnMut(Value) -> Result<()>,
+{
+    let mut callback_error = None;
+    let mut deserializer = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+    let visitor = ElementVisitor {
+        on_element: &mut on_element,
+        callback_error: &mut callback_error,
+    };
+
+    let result = deserializer.deserialize_seq(visitor).and_then(|count| {
+        deserializer.end()?;
+        Ok(count)
+    });
+    if let Some(e) = callback_error {
+        return Err(e);
+    }
+    Ok(result?)
+}
+
+struct ElementVisitor<'a, F> {
+    on_element: &'a mut F,
+    callback_error: &'a mut Option<anyhow::Error>,
+}
+
+impl<'de, F> Visitor<'de> for ElementVisitor<'_, F>
+where
+    F: FnMut(Value) -> Result<()>,
+{
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+        let mut count = 0;
+        while let Some(element) = seq.next_element::<Value>()? {
+            if let Err(e) = (self.on_element)(element) {
+                *self.callback_error = Some(e);
+                return Err(de::Error::custom("element callback failed"));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Parses JSON like `serde_json::from_str`, but rejects objects that repeat a
+/// key instead of silently keeping the last value.
+pub fn from_str_strict(input: &str) -> serde_json::Result<Value> {
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    let StrictValue(value) = StrictValue::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+struct StrictValue(Value);
+
+impl<'de> Deserialize<'de> for StrictValue {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(StrictVisitor).map(StrictValue)
+    }
+}
+
+struct StrictVisitor;
+
+impl<'de> Visitor<'de> for StrictVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut elements = Vec::new();
+        while let Some(StrictValue(element)) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(Value::Array(elements))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if object.contains_key(&key) {
+                return Err(de::Error::custom(format!("duplicate key `{}`", key)));
+            }
+            let StrictValue(value) = map.next_value()?;
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+/// Splits a pointer into unescaped reference tokens (`~1` is `/`, `~0` is `~`).
+fn parse_pointer(pointer: &str) -> Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        bail!("Invalid JSON pointer '{}': must start with '/'", pointer);
+    };
+
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn join_pointer(tokens: &[String]) -> String {
+    tokens
+        .iter()
+        .map(|token| format!("/{}", token.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Parses an array index token; `-` refers to the slot just past the end.
+fn array_index(token: &str, len: usize, pointer: &str) -> Result<usize> {
+    if token == "-" {
+        return Ok(len);
+    }
+    match token.parse() {
+        Ok(index) => Ok(index),
+        Err(_) => bail!("Cannot set {}: '{}' is not an array index", pointer, token),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_output_is_byte_identical() {
+        let a: Value =
+            serde_json::from_str(r#"{"b": [1.0, 2e1, -0.0], "a": {"y": "\u00e9", "x": 1e21}}"#)
+                .unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{ "a": { "x": 1E+21, "y": "é" }, "b": [ 1, 20, 0 ] }"#)
+                .unwrap();
+
+        let canonical = to_canonical_string(&a).unwrap();
+        assert_eq!(canonical, to_canonical_string(&b).unwrap());
+        assert_eq!(canonical, r#"{"a":{"x":1e+21,"y":"é"},"b":[1,20,0]}"#);
+    }
+
+    #[test]
+    fn test_canonical_numbers() {
+        let cases = [
+            (0.5, "0.5"),
+            (123.456, "123.456"),
+            (1e-7, "1e-7"),
+            (0.000001, "0.000001"),
+            (-1.5e300, "-1.5e+300"),
+            (4.5e15, "4500000000000000"),
+        ];
+        for (number, expected) in cases {
+            assert_eq!(canonical_number(number), expected);
+        }
+    }
+
+    #[test]
+    fn test_strict_rejects_duplicate_keys() {
+        let nested = r#"{"a": {"b": 1, "b": 2}}"#;
+        let error = from_str_strict(nested).unwrap_err();
+        assert!(error.to_string().contains("duplicate key `b`"));
+
+        let value = from_str_strict(r#"{"a": [{"b": 1}, {"b": 2.5}], "c": null}"#).unwrap();
+        assert_eq!(value, json!({"a": [{"b": 1}, {"b": 2.5}], "c": null}));
+    }
+
+    #[test]
+    fn test_set_creates_intermediate_objects() {
+        let mut value = json!({});
+        set(&mut value, "/x/y", json!(1)).unwrap();
+
+        assert_eq!(value, json!({"x": {"y": 1}}));
+        assert_eq!(get(&value, "/x/y"), Some(&json!(1)));
+        assert_eq!(get(&value, "/x/z"), None);
+    }
+
+    #[test]
+    fn test_set_into_arrays_and_escaped_keys() {
+        let mut value = json!({"items": [1, 2]});
+        set(&mut value, "/items/0", json!("first")).unwrap();
+        set(&mut value, "/items/-", json!(3)).unwrap();
+        set(&mut value, "/a~1b", json!(true)).unwrap();
+
+        assert_eq!(value, json!({"items": ["first", 2, 3], "a/b": true}));
+        assert!(set(&mut value, "/items/9", json!(0)).is_err());
+    }
+
+    #[test]
+    fn test_remove_returns_removed_node() {
+        let mut value = json!({"users": [{"name": "a"}, {"name": "b"}], "count": 2});
+
+        assert_eq!(remove(&mut value, "/users/0"), Some(json!({"name": "a"})));
+        assert_eq!(remove(&mut value, "/count"), Some(json!(2)));
+        assert_eq!(value, json!({"users": [{"name": "b"}]}));
+
+        assert_eq!(remove(&mut value, "/users/5"), None);
+        assert_eq!(remove(&mut value, "/missing/key"), None);
+        assert_eq!(value, json!({"users": [{"name": "b"}]}));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let value = json!({"a": {"b": [1, {"c": true}]}, "d": "x", "e": {}, "f": null});
+        let flat = flatten(&value);
+
+        let keys: Vec<&str> = flat.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["a.b.0", "a.b.1.c", "d", "e", "f"]);
+        assert_eq!(flat["a.b.1.c"], json!(true));
+        assert_eq!(flat["e"], json!({}));
+        assert_eq!(flatten(&json!(5))[""], json!(5));
+    }
+
+    #[test]
+    fn test_to_string_indented() {
+        let value = json!({"a": {"b": 1}});
+
+        assert_eq!(
+            to_string_indented(&value, "    ").unwrap(),
+            "{\n    \"a\": {\n        \"b\": 1\n    }\n}"
+        );
+        assert_eq!(
+            to_string_indented(&value, "  ").unwrap(),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert!(to_string_indented(&value, "\t")
+            .unwrap()
+            .contains("\n\t\"a\""));
+    }
+
+    #[test]
+    fn test_for_each_array_element() {
+        let input = r#" [ {"id": 1}, 2, "three", [4], null ] "#;
+        let mut seen = Vec::new();
+        let count = for_each_array_element(input.as_bytes(), |element| {
+            seen.push(element);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(count, 5);
+        assert_eq!(seen[0], json!({"id": 1}));
+        assert_eq!(seen[4], Value::Null);
+
+        assert!(for_each_array_element(&b"{\"a\": 1}"[..], |_| Ok(())).is_err());
+        let err = for_each_array_element(&b"[1, 2, 3]"[..], |element| {
+            if element == json!(2) {
+                bail!("stop at two");
+            }
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "stop at two");
+    }
+
+    #[test]
+    fn test_set_through_scalar_errors() {
+        let mut value = json!({"a": 5});
+        let err = set(&mut value, "/a/b/c", json!(1)).unwrap_err();
+
+        assert_eq!(err.to_string(), "Cannot set /a/b/c: '/a' is a number");
+        assert_eq!(value, json!({"a": 5}));
+        assert!(set(&mut value, "no-slash", json!(1)).is_err());
+    }
+}
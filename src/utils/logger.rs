@@ -1,10 +1,34 @@
-use chrono::Local;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::cell::Cell;
 use std::io::Write;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+/// Process-wide record counter so events within the same millisecond stay ordered.
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Set once any logger emits a `Warn` or more severe record, for `--fail-on-warn`.
+static WARNING_EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether any logger in this process has emitted a warning or error.
+pub fn warnings_emitted() -> bool {
+    WARNING_EMITTED.load(Ordering::Relaxed)
+}
+
+/// Indent unit per open `Logger::scope` in human-format records.
+const SCOPE_INDENT: &str = "  ";
+
+thread_local! {
+    /// Number of `ScopeGuard`s alive on this thread.
+    static SCOPE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[allow(dead_code)]
 pub enum LogLevel {
@@ -15,10 +39,51 @@ pub enum LogLevel {
     Fatal = 4,
 }
 
+impl LogLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Fatal => "fatal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(dead_code)]
+pub enum LogFormat {
+    #[default]
+    Human,
+    Json,
+    Logfmt,
+}
+
+/// How `Logger::progress` reports: an interactive bar, or JSON lines on the
+/// error sink (stderr) for a parent process to render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[allow(dead_code)]
+pub enum ProgressFormat {
+    #[default]
+    Bar,
+    Json,
+}
+
 pub struct Logger {
     level: LogLevel,
     use_colors: bool,
+    show_timestamp: bool,
+    format: LogFormat,
+    output_is_tty: bool,
     output: Mutex<Box<dyn Write + Send>>,
+    /// Sink for `Error`/`Fatal` records; `None` sends them to `output` as well.
+    error_output: Option<Mutex<Box<dyn Write + Send>>>,
+    /// Colorize records sent to `error_output`, decided separately since
+    /// stdout and stderr are often redirected independently.
+    error_use_colors: bool,
+    progress_format: ProgressFormat,
+    last_milestone: Mutex<Option<u64>>,
 }
 
 #[allow(dead_code)]
@@ -27,7 +92,14 @@ impl Logger {
         Self {
             level,
             use_colors: atty::is(atty::Stream::Stdout),
+            show_timestamp: true,
+            format: LogFormat::Human,
+            output_is_tty: atty::is(atty::Stream::Stdout),
             output: Mutex::new(Box::new(std::io::stdout())),
+            error_output: Some(Mutex::new(Box::new(std::io::stderr()))),
+            error_use_colors: atty::is(atty::Stream::Stderr),
+            progress_format: ProgressFormat::Bar,
+            last_milestone: Mutex::new(None),
         }
     }
 
@@ -35,10 +107,52 @@ impl Logger {
         Self {
             level,
             use_colors,
+            show_timestamp: true,
+            format: LogFormat::Human,
+            output_is_tty: atty::is(atty::Stream::Stdout),
             output: Mutex::new(Box::new(std::io::stdout())),
+            error_output: Some(Mutex::new(Box::new(std::io::stderr()))),
+            error_use_colors: use_colors,
+            progress_format: ProgressFormat::Bar,
+            last_milestone: Mutex::new(None),
         }
     }
 
+    /// Omits the `[timestamp]` prefix when the surrounding environment already adds one.
+    pub fn with_timestamp(mut self, show_timestamp: bool) -> Self {
+        self.show_timestamp = show_timestamp;
+        self
+    }
+
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_progress_format(mut self, progress_format: ProgressFormat) -> Self {
+        self.progress_format = progress_format;
+        self
+    }
+
+    /// Replaces the sink for `Debug`, `Info` and `Warn` records (stdout by default).
+    pub fn with_output<W: Write + Send + 'static>(mut self, output: W) -> Self {
+        self.output = Mutex::new(Box::new(output));
+        self
+    }
+
+    /// Replaces the sink for `Error` and `Fatal` records (stderr by default).
+    pub fn with_error_output<W: Write + Send + 'static>(mut self, output: W) -> Self {
+        self.error_output = Some(Mutex::new(Box::new(output)));
+        self
+    }
+
+    /// Sends records of every level to a single sink.
+    pub fn with_writer<W: Write + Send + 'static>(mut self, output: W) -> Self {
+        self.output = Mutex::new(Box::new(output));
+        self.error_output = None;
+        self
+    }
+
     pub fn debug(&self, message: &str) {
         self.log(LogLevel::Debug, message);
     }
@@ -64,10 +178,69 @@ impl Logger {
             return;
         }
 
-        let timestamp = Local::now().format("%Y-%m-%dT%H:%M:%S%.3f");
+        if severity >= LogLevel::Warn {
+            WARNING_EMITTED.store(true, Ordering::Relaxed);
+        }
+        let (sink, use_colors) = match &self.error_output {
+            Some(error_output) if severity >= LogLevel::Error => {
+                (error_output, self.error_use_colors)
+            }
+            _ => (&self.output, self.use_colors),
+        };
+        let formatted = self.format_record(severity, message, use_colors);
+
+        let mut output = sink.lock().unwrap();
+        // A closed pipe (`| head`) must not turn logging into a panic.
+        let _ = writeln!(output, "{}", formatted);
+        // Flush every record so tail lines survive an abrupt exit
+        let _ = output.flush();
+    }
+
+    pub fn flush(&self) {
+        let _ = self.output.lock().unwrap().flush();
+        if let Some(error_output) = &self.error_output {
+            let _ = error_output.lock().unwrap().flush();
+        }
+    }
+
+    fn format_record(&self, severity: LogLevel, message: &str, use_colors: bool) -> String {
+        match self.format {
+            LogFormat::Human => self.format_human(severity, message, use_colors),
+            LogFormat::Json => self.format_json(severity, message),
+            LogFormat::Logfmt => self.format_logfmt(severity, message),
+        }
+    }
+
+    fn format_json(&self, severity: LogLevel, message: &str) -> String {
+        let mut record = serde_json::Map::new();
+        if self.show_timestamp {
+            record.insert("timestamp".into(), Local::now().to_rfc3339().into());
+        }
+        record.insert(
+            "seq".into(),
+            NEXT_SEQ.fetch_add(1, Ordering::Relaxed).into(),
+        );
+        record.insert("level".into(), severity.name().into());
+        record.insert("message".into(), message.into());
+        serde_json::Value::Object(record).to_string()
+    }
+
+    fn format_logfmt(&self, severity: LogLevel, message: &str) -> String {
+        let mut pairs = Vec::new();
+        if self.show_timestamp {
+            pairs.push(format!("ts={}", Local::now().to_rfc3339()));
+        }
+        pairs.push(format!("level={}", severity.name()));
+        pairs.push(format!("msg={}", logfmt_value(message)));
+        pairs.join(" ")
+    }
+
+    fn format_human(&self, severity: LogLevel, message: &str, use_colors: bool) -> String {
         let severity_str = format!("{:?}", severity).to_uppercase();
+        let indent = SCOPE_INDENT.repeat(SCOPE_DEPTH.with(Cell::get));
+        let message = format!("{}{}", indent, message);
 
-        let formatted = if self.use_colors {
+        let line = if use_colors {
             let colored_severity = match severity {
                 LogLevel::Debug => severity_str.cyan(),
                 LogLevel::Info => severity_str.green(),
@@ -75,13 +248,17 @@ impl Logger {
                 LogLevel::Error => severity_str.red(),
                 LogLevel::Fatal => severity_str.magenta(),
             };
-            format!("[{}] {} | {}", timestamp, colored_severity, message)
+            format!("{} | {}", colored_severity, message)
         } else {
-            format!("[{}] {:5} | {}", timestamp, severity_str, message)
+            format!("{:5} | {}", severity_str, message)
         };
 
-        let mut output = self.output.lock().unwrap();
-        writeln!(output, "{}", formatted).unwrap();
+        if self.show_timestamp {
+            let timestamp = Local::now().format("%Y-%m-%dT%H:%M:%S%.3f");
+            format!("[{}] {}", timestamp, line)
+        } else {
+            line
+        }
     }
 
     pub fn with_timing<F, R>(&self, message: &str, f: F) -> R
@@ -103,7 +280,31 @@ impl Logger {
         result
     }
 
+    /// Logs `message` and indents this thread's later human-format records one
+    /// level deeper until the returned guard is dropped.
+    pub fn scope(&self, message: &str) -> ScopeGuard {
+        self.info(message);
+        SCOPE_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        ScopeGuard {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Starts timing a discrete operation; call `finish` on the result to log it.
+    pub fn operation(&self, name: &str) -> OperationLog<'_> {
+        OperationLog {
+            logger: self,
+            name: name.to_string(),
+            start: Instant::now(),
+        }
+    }
+
     pub fn progress(&self, current: usize, total: usize, message: &str) {
+        if self.progress_format == ProgressFormat::Json {
+            self.progress_event(current, total, message);
+            return;
+        }
+
         let _percentage = (current as f64 / total as f64 * 100.0) as u64;
         let pb = ProgressBar::new(total as u64);
 
@@ -124,6 +325,94 @@ impl Logger {
             println!();
         }
     }
+
+    /// Writes `{"event":"progress",...}` as one line to the error sink.
+    fn progress_event(&self, current: usize, total: usize, message: &str) {
+        let event = serde_json::json!({
+            "event": "progress",
+            "current": current,
+            "total": total,
+            "message": message,
+        });
+        let sink = self.error_output.as_ref().unwrap_or(&self.output);
+        let mut output = sink.lock().unwrap();
+        let _ = writeln!(output, "{}", event);
+        let _ = output.flush();
+    }
+
+    /// Prints a single-line percentage without `indicatif`. Interactive output is
+    /// rewritten in place; plain non-tty output only reports 0/25/50/75/100%.
+    pub fn progress_percent(&self, current: usize, total: usize, message: &str) {
+        if self.progress_format == ProgressFormat::Json {
+            self.progress_event(current, total, message);
+            return;
+        }
+
+        let percent = (current.min(total) * 100).checked_div(total).unwrap_or(100) as u64;
+        let done = current >= total;
+
+        let mut output = self.output.lock().unwrap();
+        let mut last_milestone = self.last_milestone.lock().unwrap();
+
+        if self.use_colors || self.output_is_tty {
+            let _ = write!(output, "\r{}: {:3}%", message, percent);
+            if done {
+                let _ = writeln!(output);
+            }
+        } else {
+            let milestone = percent / 25 * 25;
+            if last_milestone.is_none_or(|last| milestone > last) {
+                let _ = writeln!(output, "{}: {}%", message, milestone);
+                *last_milestone = Some(milestone);
+            }
+        }
+        let _ = output.flush();
+
+        if done {
+            *last_milestone = None;
+        }
+    }
+}
+
+/// Closes a `Logger::scope` when dropped. Tied to the thread that opened it.
+#[allow(dead_code)]
+pub struct ScopeGuard {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPE_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum OperationStatus {
+    Ok,
+    Error,
+}
+
+#[allow(dead_code)]
+pub struct OperationLog<'a> {
+    logger: &'a Logger,
+    name: String,
+    start: Instant,
+}
+
+#[allow(dead_code)]
+impl OperationLog<'_> {
+    pub fn finish(self, status: OperationStatus) {
+        let elapsed = format_duration(self.start.elapsed());
+        match status {
+            OperationStatus::Ok => self
+                .logger
+                .info(&format!("{} status=ok duration={}", self.name, elapsed)),
+            OperationStatus::Error => self
+                .logger
+                .warn(&format!("{} status=error duration={}", self.name, elapsed)),
+        }
+    }
 }
 
 impl Default for Logger {
@@ -132,6 +421,25 @@ impl Default for Logger {
     }
 }
 
+/// Quotes a logfmt value when it is empty or contains spaces, quotes, `=` or control characters.
+fn logfmt_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| matches!(c, ' ' | '=' | '"' | '\\') || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
 #[allow(dead_code)]
 fn format_duration(d: Duration) -> String {
     if d.as_secs() > 60 {
@@ -152,18 +460,27 @@ pub struct FileLogger {
 
 #[allow(dead_code)]
 impl FileLogger {
+    /// `filename` may contain strftime patterns such as `app-%Y-%m-%d.log`,
+    /// expanded with the local time when the file is opened.
     pub fn new(filename: &str) -> Self {
         let file = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
-            .open(filename)
+            .open(expand_filename(filename, Local::now()))
             .unwrap();
 
         Self {
             logger: Logger {
                 level: LogLevel::Info,
                 use_colors: false,
+                show_timestamp: true,
+                format: LogFormat::Human,
+                output_is_tty: false,
                 output: Mutex::new(Box::new(file)),
+                error_output: None,
+                error_use_colors: false,
+                progress_format: ProgressFormat::Bar,
+                last_milestone: Mutex::new(None),
             },
         }
     }
@@ -175,6 +492,29 @@ impl FileLogger {
     pub fn error(&self, message: &str) {
         self.logger.error(message);
     }
+
+    pub fn flush(&self) {
+        self.logger.flush();
+    }
+}
+
+/// Expands strftime patterns in `filename`; names without `%`, or with an
+/// invalid pattern, are used verbatim.
+fn expand_filename(filename: &str, now: DateTime<Local>) -> String {
+    if !filename.contains('%') {
+        return filename.to_string();
+    }
+    let items: Vec<Item> = StrftimeItems::new(filename).collect();
+    if items.contains(&Item::Error) {
+        return filename.to_string();
+    }
+    now.format_with_items(items.into_iter()).to_string()
+}
+
+impl Drop for FileLogger {
+    fn drop(&mut self) {
+        self.flush();
+    }
 }
 
 #[allow(dead_code)]
@@ -242,6 +582,273 @@ mod tests {
         assert_eq!(result, 42);
     }
 
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn buffered_logger(use_colors: bool, output_is_tty: bool) -> (Logger, SharedBuffer) {
+        let buffer = SharedBuffer::default();
+        let logger = Logger {
+            level: LogLevel::Info,
+            use_colors,
+            show_timestamp: true,
+            format: LogFormat::Human,
+            output_is_tty,
+            output: Mutex::new(Box::new(buffer.clone())),
+            error_output: None,
+            error_use_colors: use_colors,
+            progress_format: ProgressFormat::Bar,
+            last_milestone: Mutex::new(None),
+        };
+        (logger, buffer)
+    }
+
+    #[test]
+    fn test_nested_scopes_indent() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_timestamp(false);
+        {
+            let _outer = logger.scope("deploy");
+            logger.info("outer step");
+            {
+                let _inner = logger.scope("upload");
+                logger.info("inner step");
+            }
+            logger.info("back out");
+        }
+        logger.info("done");
+
+        assert_eq!(
+            buffer.contents(),
+            "INFO  | deploy\n\
+             INFO  |   outer step\n\
+             INFO  |   upload\n\
+             INFO  |     inner step\n\
+             INFO  |   back out\n\
+             INFO  | done\n"
+        );
+    }
+
+    #[test]
+    fn test_json_progress_events() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_progress_format(ProgressFormat::Json);
+        logger.progress(1, 4, "Copying");
+        logger.progress_percent(4, 4, "Copying");
+
+        let events: Vec<serde_json::Value> = buffer
+            .contents()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["event"], "progress");
+        assert_eq!(
+            (events[0]["current"].as_u64(), events[0]["total"].as_u64()),
+            (Some(1), Some(4))
+        );
+        assert_eq!(events[1]["current"], 4);
+    }
+
+    #[test]
+    fn test_without_timestamp() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_timestamp(false);
+        logger.info("ready");
+
+        assert_eq!(buffer.contents(), "INFO  | ready\n");
+
+        let (logger, buffer) = buffered_logger(false, false);
+        logger.info("ready");
+        assert!(buffer.contents().starts_with('['));
+    }
+
+    #[test]
+    fn test_errors_route_to_error_output() {
+        let (stdout, stderr) = (SharedBuffer::default(), SharedBuffer::default());
+        let logger = Logger::new_with_options(LogLevel::Info, false)
+            .with_timestamp(false)
+            .with_output(stdout.clone())
+            .with_error_output(stderr.clone());
+
+        logger.info("loaded");
+        logger.warn("slow");
+        logger.error("failed");
+        logger.fatal("gave up");
+
+        assert_eq!(stdout.contents(), "INFO  | loaded\nWARN  | slow\n");
+        assert_eq!(stderr.contents(), "ERROR | failed\nFATAL | gave up\n");
+
+        let combined = SharedBuffer::default();
+        let logger = logger.with_writer(combined.clone());
+        logger.info("one");
+        logger.error("two");
+        assert_eq!(combined.contents(), "INFO  | one\nERROR | two\n");
+    }
+
+    #[test]
+    fn test_error_sink_colors_decided_separately() {
+        let stderr = SharedBuffer::default();
+        let mut logger = Logger::new_with_options(LogLevel::Info, true)
+            .with_timestamp(false)
+            .with_output(SharedBuffer::default())
+            .with_error_output(stderr.clone());
+        // As when stdout is a terminal but stderr is redirected to a file.
+        logger.error_use_colors = false;
+
+        logger.error("failed");
+        assert_eq!(stderr.contents(), "ERROR | failed\n");
+    }
+
+    #[test]
+    fn test_closed_pipe_does_not_panic() {
+        struct ClosedPipe;
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        let logger = Logger::new_with_options(LogLevel::Info, false).with_writer(ClosedPipe);
+        logger.info("dropped");
+        logger.error("dropped");
+        logger.progress_percent(1, 2, "copy");
+
+        let logger = logger.with_progress_format(ProgressFormat::Json);
+        logger.progress(1, 2, "copy");
+    }
+
+    #[test]
+    fn test_operation_log() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_timestamp(false);
+
+        logger.operation("load-config").finish(OperationStatus::Ok);
+        logger.operation("sync").finish(OperationStatus::Error);
+
+        let output = buffer.contents();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("INFO  | load-config status=ok duration="));
+        assert!(lines[1].starts_with("WARN  | sync status=error duration="));
+    }
+
+    #[test]
+    fn test_logfmt_format() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_format(LogFormat::Logfmt);
+        logger.warn("disk almost full");
+
+        let output = buffer.contents();
+        assert!(output.starts_with("ts="));
+        assert!(output.ends_with(" level=warn msg=\"disk almost full\"\n"));
+
+        assert_eq!(logfmt_value("plain"), "plain");
+        assert_eq!(logfmt_value(r#"say "hi""#), r#""say \"hi\"""#);
+    }
+
+    #[test]
+    fn test_json_format() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_format(LogFormat::Json);
+        logger.error("boom");
+
+        let record: serde_json::Value = serde_json::from_str(buffer.contents().trim()).unwrap();
+        assert_eq!(record["level"], "error");
+        assert_eq!(record["message"], "boom");
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(record["timestamp"].as_str().unwrap()).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_json_seq_increases() {
+        let (logger, buffer) = buffered_logger(false, false);
+        let logger = logger.with_format(LogFormat::Json);
+        logger.info("first");
+        logger.info("second");
+
+        let seqs: Vec<u64> = buffer
+            .contents()
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                record["seq"].as_u64().unwrap()
+            })
+            .collect();
+        assert_eq!(seqs.len(), 2);
+        assert!(seqs[1] > seqs[0]);
+    }
+
+    #[test]
+    fn test_progress_percent_milestones() {
+        let (logger, buffer) = buffered_logger(false, false);
+        for i in 0..=10 {
+            logger.progress_percent(i, 10, "Copying");
+        }
+
+        let output = buffer.contents();
+        assert_eq!(
+            output,
+            "Copying: 0%\nCopying: 25%\nCopying: 50%\nCopying: 75%\nCopying: 100%\n"
+        );
+    }
+
+    #[test]
+    fn test_progress_percent_interactive() {
+        let (logger, buffer) = buffered_logger(true, true);
+        logger.progress_percent(1, 2, "Copying");
+        logger.progress_percent(2, 2, "Copying");
+
+        let output = buffer.contents();
+        assert_eq!(output, "\rCopying:  50%\rCopying: 100%\n");
+    }
+
+    #[test]
+    fn test_file_logger_persists_on_drop() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let log_path = dir.path().join("app.log");
+
+        let logger = FileLogger::new(log_path.to_str().unwrap());
+        logger.info("written before drop");
+        drop(logger);
+
+        let content = std::fs::read_to_string(&log_path).unwrap();
+        assert!(content.contains("INFO  | written before drop"));
+    }
+
+    #[test]
+    fn test_file_logger_date_pattern() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pattern = dir.path().join("app-%Y-%m-%d.log");
+
+        let logger = FileLogger::new(pattern.to_str().unwrap());
+        logger.info("dated");
+        drop(logger);
+
+        let expected = format!("app-{}.log", Local::now().format("%Y-%m-%d"));
+        assert!(dir.path().join(expected).exists());
+        assert_eq!(expand_filename("plain.log", Local::now()), "plain.log");
+        assert_eq!(expand_filename("bad-%Q.log", Local::now()), "bad-%Q.log");
+    }
+
     #[test]
     fn test_format_duration() {
         assert!(format_duration(Duration::from_millis(500)).contains("ms"));
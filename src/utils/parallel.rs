@@ -0,0 +1,61 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Applies `f` to every item on up to `jobs` threads and returns the results in
+/// input order. `jobs` of 0 uses one thread per available CPU; 1 runs inline.
+pub fn map_ordered<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = match jobs {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    }
+    .min(items.len());
+    if jobs <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_keep_input_order() {
+        let items: Vec<u64> = (0..200).collect();
+        let square = |n: &u64| {
+            std::thread::sleep(std::time::Duration::from_micros(200 - n));
+            n * n
+        };
+
+        let serial = map_ordered(&items, 1, square);
+        assert_eq!(map_ordered(&items, 4, square), serial);
+        assert_eq!(map_ordered(&items, 0, square), serial);
+        assert!(map_ordered(&[] as &[u64], 4, square).is_empty());
+    }
+}
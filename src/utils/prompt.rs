@@ -0,0 +1,46 @@
+use std::io::{BufRead, Write};
+
+/// Asks a y/N question on stderr and reads the answer from stdin. Returns true
+/// straight away when `assume_yes` (the `--yes` flag) is set. When stdin is not
+/// a terminal nobody can answer, so it returns the safe "no".
+#[allow(dead_code)]
+pub fn confirm(message: &str, assume_yes: bool) -> bool {
+    if assume_yes {
+        return true;
+    }
+    if !atty::is(atty::Stream::Stdin) {
+        return false;
+    }
+    confirm_with(message, std::io::stdin().lock(), std::io::stderr())
+}
+
+/// `confirm` against explicit streams: only `y` or `yes` (any case) count as yes.
+#[allow(dead_code)]
+pub fn confirm_with<R: BufRead, W: Write>(message: &str, mut input: R, mut output: W) -> bool {
+    let _ = write!(output, "{} [y/N] ", message);
+    let _ = output.flush();
+
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_with_answers() {
+        let mut prompt = Vec::new();
+        assert!(confirm_with("Delete 3 files?", &b"y\n"[..], &mut prompt));
+        assert_eq!(String::from_utf8(prompt).unwrap(), "Delete 3 files? [y/N] ");
+
+        assert!(confirm_with("Delete?", &b"YES\n"[..], std::io::sink()));
+        assert!(!confirm_with("Delete?", &b"\n"[..], std::io::sink()));
+        assert!(!confirm_with("Delete?", &b"nope\n"[..], std::io::sink()));
+        assert!(!confirm_with("Delete?", &b""[..], std::io::sink()));
+        assert!(confirm("Delete?", true));
+    }
+}
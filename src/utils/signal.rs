@@ -0,0 +1,13 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Installs a Ctrl-C handler that sets the returned flag instead of killing the
+/// process, so long-running loops can stop cleanly and report. A process can
+/// only install one handler, so only the CLI entry point should call this.
+pub fn install_interrupt_handler() -> Result<Arc<AtomicBool>> {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))?;
+    Ok(interrupted)
+}
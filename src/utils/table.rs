@@ -0,0 +1,109 @@
+/// Plain-text table with columns padded to their widest cell.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+}
+
+const COLUMN_GAP: &str = "  ";
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Header row, rendered above a dashed rule.
+    #[allow(dead_code)]
+    pub fn with_header<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn add_row<I, S>(&mut self, cells: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Renders one line per row; the last column is not padded, so lines carry no
+    /// trailing whitespace. Rows may have fewer cells than others.
+    pub fn render(&self) -> String {
+        let all_rows = || self.header.iter().chain(&self.rows);
+        let mut widths: Vec<usize> = Vec::new();
+        for row in all_rows() {
+            for (column, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(column) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        let mut output = String::new();
+        if let Some(header) = &self.header {
+            output.push_str(&render_row(header, &widths));
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            output.push_str(&render_row(&rule, &widths));
+        }
+        for row in &self.rows {
+            output.push_str(&render_row(row, &widths));
+        }
+        output
+    }
+}
+
+fn render_row(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (column, cell) in cells.iter().enumerate() {
+        if column + 1 == cells.len() {
+            line.push_str(cell);
+        } else {
+            let padding = widths[column] - cell.chars().count();
+            line.push_str(cell);
+            line.push_str(&" ".repeat(padding));
+            line.push_str(COLUMN_GAP);
+        }
+    }
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_column_alignment() {
+        let mut table = Table::new().with_header(["Name", "Size"]);
+        table
+            .add_row(["a.txt", "12"])
+            .add_row(["résumé.pdf", "3400"]);
+
+        assert_eq!(
+            table.render(),
+            "Name        Size\n\
+             ----------  ----\n\
+             a.txt       12\n\
+             résumé.pdf  3400\n"
+        );
+    }
+
+    #[test]
+    fn test_without_header() {
+        let mut table = Table::new();
+        table
+            .add_row(["Version:", "1.0.0"])
+            .add_row(["Platform:", "linux"]);
+
+        assert_eq!(table.render(), "Version:   1.0.0\nPlatform:  linux\n");
+        assert_eq!(Table::new().render(), "");
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+#[allow(dead_code)]
+pub fn render(template: &str, data: &Value, allow_missing: bool) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            bail!("Unclosed placeholder: {{{{{}", after_open);
+        };
+
+        let key = after_open[..end].trim();
+        match resolve(data, key) {
+            Some(value) => output.push_str(&value_to_string(value)),
+            None if allow_missing => {}
+            None => bail!("Missing template key: {}", key),
+        }
+
+        rest = &after_open[end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn resolve<'a>(data: &'a Value, key: &str) -> Option<&'a Value> {
+    if key.is_empty() {
+        return None;
+    }
+
+    key.split('.')
+        .try_fold(data, |current, segment| match current {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_simple_placeholder() {
+        let output = render("Hello {{name}}", &json!({"name": "X"}), false).unwrap();
+        assert_eq!(output, "Hello X");
+    }
+
+    #[test]
+    fn test_nested_path() {
+        let data = json!({"user": {"emails": ["a@example.com", "b@example.com"]}, "count": 2});
+        let output = render("{{ user.emails.1 }} ({{count}})", &data, false).unwrap();
+        assert_eq!(output, "b@example.com (2)");
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let data = json!({});
+        assert!(render("Hi {{name}}", &data, false).is_err());
+        assert_eq!(render("Hi {{name}}!", &data, true).unwrap(), "Hi !");
+    }
+
+    #[test]
+    fn test_unclosed_placeholder() {
+        assert!(render("Hi {{name", &json!({"name": "X"}), false).is_err());
+    }
+}
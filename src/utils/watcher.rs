@@ -0,0 +1,190 @@
+use anyhow::{bail, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::utils::file_handler::{FileError, FileHandler};
+
+/// How long watch loops block on notifications before re-checking timeouts.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Filesystem notifications for a set of files and directories. Directories are
+/// watched recursively; for a file its parent is watched so editors that replace
+/// the file via rename are still seen, and a missing file can reappear.
+pub struct PathWatcher {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+}
+
+impl PathWatcher {
+    pub fn new(paths: &[PathBuf]) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+
+        for path in paths {
+            if path.is_dir() {
+                watcher.watch(path, RecursiveMode::Recursive)?;
+                continue;
+            }
+
+            let parent = match path.parent() {
+                Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+                Some(parent) => parent,
+                None => Path::new("/"),
+            };
+            if !parent.is_dir() {
+                bail!(FileError::NotFound(path.display().to_string()));
+            }
+            watcher.watch(parent, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Blocks for up to `wait` and returns the paths named by any events that
+    /// arrived, or an empty list if none did. Events already queued behind the
+    /// first one are drained too, so a burst of writes is reported once.
+    pub fn changed_paths(&self, wait: Duration) -> Result<Vec<PathBuf>> {
+        let first = match self.events.recv_timeout(wait) {
+            Ok(event) => event?,
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => bail!("File watcher stopped"),
+        };
+
+        let mut paths = first.paths;
+        for event in self.events.try_iter() {
+            paths.extend(event?.paths);
+        }
+        Ok(paths)
+    }
+}
+
+/// Watches a single file and only reports changes to its content, ignoring
+/// metadata-only events such as `touch`.
+#[allow(dead_code)]
+pub struct ContentWatcher {
+    path: PathBuf,
+    algorithm: String,
+    last_hash: Option<String>,
+}
+
+#[allow(dead_code)]
+impl ContentWatcher {
+    pub fn new<P: AsRef<Path>>(path: P, algorithm: &str) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let last_hash = Self::hash(&path, algorithm)?;
+
+        Ok(Self {
+            path,
+            algorithm: algorithm.to_string(),
+            last_hash,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Re-hashes the file and returns true if its content differs from the last check.
+    pub fn has_changed(&mut self) -> Result<bool> {
+        let hash = Self::hash(&self.path, &self.algorithm)?;
+        if hash == self.last_hash {
+            return Ok(false);
+        }
+
+        self.last_hash = hash;
+        Ok(true)
+    }
+
+    /// Blocks until `on_change` returns false or `timeout` elapses, invoking it
+    /// each time the file's content actually changes.
+    pub fn watch<F>(&mut self, timeout: Option<Duration>, mut on_change: F) -> Result<()>
+    where
+        F: FnMut(&Path) -> Result<bool>,
+    {
+        let watcher = PathWatcher::new(std::slice::from_ref(&self.path))?;
+
+        let start = Instant::now();
+        loop {
+            if timeout.is_some_and(|t| start.elapsed() >= t) {
+                return Ok(());
+            }
+
+            let file_name = self.path.file_name();
+            let paths = watcher.changed_paths(POLL_INTERVAL)?;
+            if !paths.iter().any(|p| p.file_name() == file_name) {
+                continue;
+            }
+
+            if self.has_changed()? && !on_change(&self.path)? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn hash(path: &Path, algorithm: &str) -> Result<Option<String>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        FileHandler::checksum(path, algorithm).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path) {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now()).unwrap();
+    }
+
+    #[test]
+    fn test_touch_does_not_count_as_change() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("watched.txt");
+        FileHandler::write(&file_path, "original").unwrap();
+
+        let mut watcher = ContentWatcher::new(&file_path, "sha256").unwrap();
+        touch(&file_path);
+        assert!(!watcher.has_changed().unwrap());
+
+        FileHandler::write(&file_path, "edited").unwrap();
+        assert!(watcher.has_changed().unwrap());
+        assert!(!watcher.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_watch_fires_only_on_content_change() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("watched.txt");
+        FileHandler::write(&file_path, "original").unwrap();
+
+        let mut watcher = ContentWatcher::new(&file_path, "sha256").unwrap();
+        let writer_path = file_path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            touch(&writer_path);
+            std::thread::sleep(Duration::from_millis(200));
+            FileHandler::atomic_write(&writer_path, "edited").unwrap();
+        });
+
+        let mut contents = Vec::new();
+        watcher
+            .watch(Some(Duration::from_secs(5)), |path| {
+                contents.push(FileHandler::read(path)?);
+                Ok(false)
+            })
+            .unwrap();
+        writer.join().unwrap();
+
+        assert_eq!(contents, vec!["edited".to_string()]);
+    }
+}
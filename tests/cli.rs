@@ -0,0 +1,155 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn cli() -> Command {
+    Command::cargo_bin("basiccli-rust").unwrap()
+}
+
+#[test]
+fn test_missing_file_json_error() {
+    let output = cli()
+        .args([
+            "--error-format",
+            "json",
+            "process",
+            "/nonexistent/data.json",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(66));
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["kind"], "not_found");
+    assert!(error["error"]
+        .as_str()
+        .unwrap()
+        .contains("/nonexistent/data.json"));
+}
+
+#[test]
+fn test_human_error_by_default() {
+    cli()
+        .args(["process", "/nonexistent/data.json"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("Error: File not found"));
+}
+
+#[test]
+fn test_benchmark_output_none_writes_only_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let results = dir.path().join("x.json");
+
+    cli()
+        .args(["benchmark", "5", "--output", "none", "--output-file"])
+        .arg(&results)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&results).unwrap()).unwrap();
+    assert!(!written["benchmarks"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_benchmark_yaml_output() {
+    let output = cli()
+        .args(["benchmark", "2", "--output", "yaml"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let yaml: serde_yaml::Value = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert!(!yaml["benchmarks"].as_sequence().unwrap().is_empty());
+}
+
+#[test]
+fn test_fail_on_warn() {
+    let args = ["benchmark", "2", "--output", "quiet"];
+
+    cli().args(args).assert().success();
+    cli()
+        .arg("--fail-on-warn")
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--fail-on-warn"));
+}
+
+#[test]
+fn test_json_progress_on_stderr() {
+    let output = cli()
+        .args([
+            "--progress-format",
+            "json",
+            "benchmark",
+            "2",
+            "--output",
+            "none",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let events: Vec<serde_json::Value> = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(!events.is_empty());
+    assert_eq!(events[0]["event"], "progress");
+    assert_eq!(events.last().unwrap()["current"], events[0]["total"]);
+}
+
+#[test]
+fn test_output_file_expands_env_vars() {
+    let dir = tempfile::TempDir::new().unwrap();
+
+    cli()
+        .env("REPORT_DIR", dir.path())
+        .args(["benchmark", "2", "--output", "none", "--output-file"])
+        .arg("$REPORT_DIR/results.json")
+        .assert()
+        .success();
+
+    assert!(dir.path().join("results.json").exists());
+}
+
+#[test]
+fn test_benchmark_output_quiet_is_deprecated() {
+    cli()
+        .args(["benchmark", "2", "--output", "quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "--output quiet is deprecated; use --output none instead",
+        ));
+}
+
+#[test]
+fn test_process_stats_json_goes_to_stderr() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("data.json");
+    std::fs::write(&file, r#"{"a": 1, "b": [true, null], "c": {"d": "x"}}"#).unwrap();
+
+    let output = cli()
+        .args(["process", "--stats-json"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r#"{"a":1,"b":[true,null],"c":{"d":"x"}}"#));
+    assert!(!stdout.contains("key_count"));
+
+    let stats: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(stats["key_count"], 3);
+    assert_eq!(stats["depth"], 2);
+    assert_eq!(stats["nodes"]["object"], 2);
+    assert_eq!(stats["nodes"]["array"], 1);
+    assert_eq!(stats["size_bytes"], 44);
+}